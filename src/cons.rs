@@ -1,21 +1,104 @@
+use std::collections::HashMap;
+use std::env;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::io::{BufWriter, IsTerminal, Write};
+use std::process::ExitCode;
+use std::sync::OnceLock;
 
 use crate::*;
-use crate::file::{FilePath, FileManResult};
+use crate::file::{FilePath, FileManLog, FileManResult};
+#[cfg(feature = "diff")]
+use crate::file::DiffLine;
 
-use chrono::Local;
+use chrono::{DateTime, Local};
+use chrono::format::{Item, StrftimeItems};
+use regex::{Captures, Regex};
 
 pub type ConsoleResult<T> = Result<T, ()>;
 
-pub trait ConsoleLogTranslator: Send {
+pub type LogSuppressor = Box<dyn Fn(&ConsoleLog) -> bool + Send>;
+
+pub const JSON_SCHEMA_VERSION: usize = 1;
+
+const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+static PATH_REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub trait ConsoleLogTranslator: Send + ConsoleLogTranslatorClone {
     fn translate(&self, lang: &str) -> ConsoleLog;
+
+    // note: 翻訳キーとフィールドの値を返す
+    // 対応していない翻訳器は None を返し, ログは翻訳済みのメッセージとして保存される
+    fn to_replay(&self) -> Option<LogReplay> {
+        return None;
+    }
 }
 
-#[derive(Clone, PartialEq)]
+// note: ログを別の言語で出力し直すための翻訳キーとフィールドの値
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogReplay {
+    pub key: String,
+    pub fields: Vec<(String, String)>,
+}
+
+// note: Box<dyn ConsoleLogTranslator> を複製するための補助トレイト
+// Clone を実装した翻訳器には自動で実装される
+pub trait ConsoleLogTranslatorClone {
+    fn clone_box(&self) -> Box<dyn ConsoleLogTranslator>;
+}
+
+impl<T: 'static + Clone + ConsoleLogTranslator> ConsoleLogTranslatorClone for T {
+    fn clone_box(&self) -> Box<dyn ConsoleLogTranslator> {
+        return Box::new(self.clone());
+    }
+}
+
+impl Clone for Box<dyn ConsoleLogTranslator> {
+    fn clone(&self) -> Box<dyn ConsoleLogTranslator> {
+        return self.clone_box();
+    }
+}
+
+// note: 複数の翻訳を改行で連結し, ひとつの説明として扱う
+// ConsoleLog::with_desc で親のログに説明として追加する
+#[derive(Clone)]
+pub struct MultiLineDesc(pub Vec<Box<dyn ConsoleLogTranslator>>);
+
+impl ConsoleLogTranslator for MultiLineDesc {
+    fn translate(&self, lang: &str) -> ConsoleLog {
+        let logs: Vec<ConsoleLog> = self.0.iter().map(|each_translator| each_translator.translate(lang)).collect();
+
+        let kind = match logs.first() {
+            Some(v) => v.kind.clone(),
+            None => ConsoleLogKind::Note,
+        };
+
+        // note: 内部の "\n\t" を残すと別々の説明として分割されてしまう
+        let lines: Vec<String> = logs.iter().map(|each_log| each_log.msg.replace("\n\t", "\n")).collect();
+
+        return ConsoleLog::new(kind, lines.join("\n"));
+    }
+}
+
+// note: 翻訳を必要としないメッセージを言語によらずそのまま返す
+// 翻訳器はログの種類も決めるため, メッセージと合わせて種類を持つ
+#[derive(Clone)]
+pub struct PlainText(pub ConsoleLogKind, pub String);
+
+impl ConsoleLogTranslator for PlainText {
+    fn translate(&self, _lang: &str) -> ConsoleLog {
+        return ConsoleLog::new(self.0.clone(), self.1.clone());
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum ConsoleLogKind {
     Error,
     Warning,
     Note,
+    // note: color は ANSI のエスケープシーケンスの色番号 (31 など)
+    Custom { name: String, color: u8, severity: u8 },
 }
 
 impl ConsoleLogKind {
@@ -30,28 +113,224 @@ impl ConsoleLogKind {
         return Some(kind);
     }
 
+    // note: "名前:色:深刻度" の形式を独自の種類として解釈する
+    fn parse_custom(s: &str) -> Result<ConsoleLogKind, InternalLog> {
+        let unknown_kind_err = InternalLog::UnknownLogKind { value: s.to_string() };
+        let parts: Vec<&str> = s.split(':').collect();
+
+        let (name, color_str, severity_str) = match parts.as_slice() {
+            [name, color_str, severity_str] if !name.is_empty() => (*name, *color_str, *severity_str),
+            _ => return Err(unknown_kind_err),
+        };
+
+        return match (color_str.parse::<u8>(), severity_str.parse::<u8>()) {
+            (Ok(color), Ok(severity)) => Ok(ConsoleLogKind::Custom { name: name.to_string(), color: color, severity: severity }),
+            _ => Err(unknown_kind_err),
+        };
+    }
+
+    // note: 深刻度の高い順に並べる
+    pub fn all() -> [ConsoleLogKind; 3] {
+        return [ConsoleLogKind::Error, ConsoleLogKind::Warning, ConsoleLogKind::Note];
+    }
+
+    // note: 独自の種類も含め, 深刻度によってエラーかどうかを判定する
+    pub fn is_error(&self) -> bool {
+        return self.get_severity() >= ConsoleLogKind::Error.get_severity();
+    }
+
+    pub fn is_warning(&self) -> bool {
+        return !self.is_error() && self.get_severity() >= ConsoleLogKind::Warning.get_severity();
+    }
+
+    // note: 値が大きいほど深刻度が高い
+    pub fn get_severity(&self) -> u8 {
+        return match self {
+            ConsoleLogKind::Error => 2,
+            ConsoleLogKind::Warning => 1,
+            ConsoleLogKind::Note => 0,
+            ConsoleLogKind::Custom { severity, .. } => *severity,
+        };
+    }
+
+    // note: 独自の種類は RGB の色を持たないため None を返す
+    fn get_log_color_rgb(&self) -> Option<(u8, u8, u8)> {
+        let rgb = match self {
+            ConsoleLogKind::Error => (255, 85, 85),
+            ConsoleLogKind::Warning => (255, 200, 0),
+            ConsoleLogKind::Note => (85, 150, 255),
+            ConsoleLogKind::Custom { .. } => return None,
+        };
+
+        return Some(rgb);
+    }
+
     fn get_log_color_num(&self) -> usize {
         return match self {
             ConsoleLogKind::Error => 31,
             ConsoleLogKind::Warning => 33,
             ConsoleLogKind::Note => 34,
+            ConsoleLogKind::Custom { color, .. } => *color as usize,
+        };
+    }
+
+    fn get_log_kind_name(&self, lang: &str) -> String {
+        let s = match (self, lang) {
+            (ConsoleLogKind::Error, "ja") => "エラー",
+            (ConsoleLogKind::Warning, "ja") => "警告",
+            (ConsoleLogKind::Note, "ja") => "注記",
+            (ConsoleLogKind::Error, _) => "err",
+            (ConsoleLogKind::Warning, _) => "warn",
+            (ConsoleLogKind::Note, _) => "note",
+            (ConsoleLogKind::Custom { name, .. }, _) => name.as_str(),
         };
+
+        return s.to_string();
     }
+}
 
-    fn get_log_kind_name(&self) -> String {
+impl Display for ConsoleLogKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let s = match self {
-            ConsoleLogKind::Error => "err",
-            ConsoleLogKind::Warning => "warn",
+            ConsoleLogKind::Error => "error",
+            ConsoleLogKind::Warning => "warning",
             ConsoleLogKind::Note => "note",
+            // note: FromStr で読み戻せるよう "名前:色:深刻度" の形式で出力する
+            ConsoleLogKind::Custom { name, color, severity } => return write!(f, "{}:{}:{}", name, color, severity),
         };
 
-        return s.to_string();
+        return write!(f, "{}", s);
+    }
+}
+
+impl FromStr for ConsoleLogKind {
+    type Err = InternalLog;
+
+    fn from_str(s: &str) -> Result<ConsoleLogKind, InternalLog> {
+        let kind = match s.to_lowercase().as_str() {
+            "error" | "err" => ConsoleLogKind::Error,
+            "warning" | "warn" => ConsoleLogKind::Warning,
+            "note" => ConsoleLogKind::Note,
+            _ => return ConsoleLogKind::parse_custom(s),
+        };
+
+        return Ok(kind);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceLocation {
+    pub file: &'static str,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}:{}:{}", self.file, self.line, self.column);
     }
 }
 
+// note: ログの生成箇所を記録する
+#[macro_export]
+macro_rules! log_here {
+    ($log:expr) => {
+        $crate::cons::ConsoleLog::with_source_loc($log, $crate::cons::SourceLocation {
+            file: file!(),
+            line: line!(),
+            column: column!(),
+        })
+    };
+}
+
+// note: derive を使わずに翻訳器の列挙型を定義する
+// 各バリアントの en/ja メッセージ内では {フィールド名} で値を埋め込める
+// 未知の言語が指定された場合は en のメッセージを使う
+#[macro_export]
+macro_rules! define_logs {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $variant:ident $({ $($field:ident: $field_ty:ty),* $(,)? })? => {
+                    kind: $kind:ident,
+                    en: $en:literal,
+                    ja: $ja:literal $(,)?
+                }
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq)]
+        $vis enum $name {
+            $(
+                $variant $({ $($field: $field_ty),* })?,
+            )*
+        }
+
+        impl $crate::cons::ConsoleLogTranslator for $name {
+            #[allow(unused_variables)]
+            fn translate(&self, lang: &str) -> $crate::cons::ConsoleLog {
+                let log = match self {
+                    $(
+                        $name::$variant $({ $($field),* })? => {
+                            let msg = match lang {
+                                "ja" => format!($ja),
+                                _ => format!($en),
+                            };
+
+                            $crate::cons::ConsoleLog::new($crate::cons::ConsoleLogKind::$kind, msg)
+                        },
+                    )*
+                };
+
+                return match self.to_replay() {
+                    Some(v) => log.with_replay(v),
+                    None => log,
+                };
+            }
+
+            fn to_replay(&self) -> Option<$crate::cons::LogReplay> {
+                return match self {
+                    $(
+                        $name::$variant $({ $($field),* })? => Some($crate::cons::LogReplay {
+                            key: concat!(stringify!($name), "::", stringify!($variant)).to_string(),
+                            fields: vec![$($((stringify!($field).to_string(), $field.to_string())),*)?],
+                        }),
+                    )*
+                };
+            }
+        }
+
+        impl $name {
+            // note: Console::register_translations に渡すと load_replay で読み戻したログを翻訳できる
+            pub fn get_replay_translations(lang: &str) -> Vec<(String, String)> {
+                return vec![
+                    $(
+                        (
+                            concat!(stringify!($name), "::", stringify!($variant)).to_string(),
+                            match lang {
+                                "ja" => $ja,
+                                _ => $en,
+                            }.to_string(),
+                        ),
+                    )*
+                ];
+            }
+        }
+    };
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct ConsoleLog {
     pub kind: ConsoleLogKind,
     pub msg: String,
+    pub source_loc: Option<SourceLocation>,
+    // note: "E0042" のような診断コード
+    pub code: Option<String>,
+    // note: append_separator で追加される区切り行で, 出力以外では無視する
+    is_separator: bool,
+    replay: Option<LogReplay>,
 }
 
 impl ConsoleLog {
@@ -59,14 +338,114 @@ impl ConsoleLog {
         return ConsoleLog {
             kind: kind,
             msg: msg,
+            source_loc: None,
+            code: None,
+            is_separator: false,
+            replay: None,
         }
     }
+
+    fn separator() -> ConsoleLog {
+        let mut log = ConsoleLog::new(ConsoleLogKind::Note, String::new());
+        log.is_separator = true;
+        return log;
+    }
+
+    pub fn is_separator(&self) -> bool {
+        return self.is_separator;
+    }
+
+    pub fn with_code(mut self, code: String) -> ConsoleLog {
+        self.code = Some(code);
+        return self;
+    }
+
+    pub fn with_source_loc(mut self, source_loc: SourceLocation) -> ConsoleLog {
+        self.source_loc = Some(source_loc);
+        return self;
+    }
+
+    pub fn with_replay(mut self, replay: LogReplay) -> ConsoleLog {
+        self.replay = Some(replay);
+        return self;
+    }
+
+    pub fn get_replay(&self) -> Option<&LogReplay> {
+        return self.replay.as_ref();
+    }
+
+    // note: 実行時に決まる種類からタイトルと説明を組み立てる
+    pub fn from_parts(kind: ConsoleLogKind, title: String, descs: Vec<String>) -> ConsoleLog {
+        let msg = descs.iter().fold(title, |msg, each_desc| msg + "\n\t" + each_desc);
+        return ConsoleLog::new(kind, msg);
+    }
+
+    // note: 翻訳したメッセージ全体をひとつの説明として追加する
+    // 内部の "\n\t" は別々の説明として分割されないよう "\n" に置き換える
+    pub fn with_desc(mut self, translator: &dyn ConsoleLogTranslator, lang: &str) -> ConsoleLog {
+        let desc = translator.translate(lang).msg.replace("\n\t", "\n");
+        self.msg = self.msg + "\n\t" + &desc;
+        return self;
+    }
+
+    // note: メッセージの 1 行目をタイトル, "\n\t" 以降の各行を説明として扱う
+    pub fn get_title(&self) -> &str {
+        return match self.msg.split_once("\n\t") {
+            Some((title, _)) => title,
+            None => &self.msg,
+        };
+    }
+
+    pub fn get_descs(&self) -> Vec<&str> {
+        return self.msg.split("\n\t").skip(1).collect();
+    }
+}
+
+pub const SUPPORTED_LANGS: [&str; 2] = ["en", "ja"];
+
+// note: Console を介さずに翻訳し, タイトルと各説明を返す
+// ConsoleLog は翻訳済みのメッセージを保持するため翻訳器を受け取る
+pub fn translate_log(translator: &dyn ConsoleLogTranslator, lang: &str) -> Result<Vec<String>, String> {
+    if !SUPPORTED_LANGS.contains(&lang) {
+        return Err(format!("unknown language `{}`", lang));
+    }
+
+    let log = translator.translate(lang);
+    let mut lines = vec![log.get_title().to_string()];
+    lines.extend(log.get_descs().iter().map(|each_desc| each_desc.to_string()));
+
+    return Ok(lines);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColorMode {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColorDepth {
+    Ansi16,
+    TrueColor,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OutputTarget {
+    Stdout,
+    Stderr,
+    File(String),
+    None,
 }
 
 #[derive(Clone, PartialEq)]
 pub enum LogFileKind {
     TextLines(Vec<String>),
     ConsoleLogs,
+    // note: 1 行につき 1 つの JSON オブジェクトを出力する
+    Ndjson,
+    // note: Console::load_replay で読み戻せる形式
+    Replayable,
 }
 
 #[derive(Clone, PartialEq)]
@@ -101,119 +480,1643 @@ impl Display for ConsoleLogLimit {
     }
 }
 
+pub struct ConsoleBuilder {
+    lang: String,
+    log_limit: ConsoleLogLimit,
+    color_mode: ColorMode,
+    min_kind: ConsoleLogKind,
+    compact: bool,
+    label: Option<String>,
+}
+
+impl Default for ConsoleBuilder {
+    fn default() -> ConsoleBuilder {
+        return ConsoleBuilder {
+            lang: "en".to_string(),
+            log_limit: ConsoleLogLimit::NoLimit,
+            color_mode: ColorMode::Auto,
+            min_kind: ConsoleLogKind::Note,
+            compact: false,
+            label: None,
+        };
+    }
+}
+
+impl ConsoleBuilder {
+    pub fn new() -> ConsoleBuilder {
+        return ConsoleBuilder::default();
+    }
+
+    pub fn lang(mut self, lang: String) -> ConsoleBuilder {
+        self.lang = lang;
+        return self;
+    }
+
+    pub fn log_limit(mut self, log_limit: ConsoleLogLimit) -> ConsoleBuilder {
+        self.log_limit = log_limit;
+        return self;
+    }
+
+    pub fn color(mut self, color_mode: ColorMode) -> ConsoleBuilder {
+        self.color_mode = color_mode;
+        return self;
+    }
+
+    pub fn min_kind(mut self, min_kind: ConsoleLogKind) -> ConsoleBuilder {
+        self.min_kind = min_kind;
+        return self;
+    }
+
+    pub fn compact(mut self, compact: bool) -> ConsoleBuilder {
+        self.compact = compact;
+        return self;
+    }
+
+    pub fn label(mut self, label: String) -> ConsoleBuilder {
+        self.label = Some(label);
+        return self;
+    }
+
+    pub fn build(self) -> Console {
+        let mut cons = Console::new(self.lang, self.log_limit);
+        cons.color_mode = self.color_mode;
+        cons.min_kind = self.min_kind;
+        cons.compact = self.compact;
+        cons.label = self.label;
+        return cons;
+    }
+}
+
+// note: 出力に影響する Console の設定の控えで, ログは含まない
+#[derive(Clone)]
+pub struct ConsoleConfig {
+    log_limit: ConsoleLogLimit,
+    label: Option<String>,
+    highlight_carets: bool,
+    compact: bool,
+    kind_labels: HashMap<(ConsoleLogKind, String), String>,
+    min_kind: ConsoleLogKind,
+    pre_quiet_min_kind: Option<ConsoleLogKind>,
+    show_source_loc: bool,
+    log_file_header: Option<String>,
+    log_file_timestamp: bool,
+    color_depth: ColorDepth,
+    max_descs_per_log: Option<usize>,
+    truncate_descs_in_files: bool,
+    color_mode: ColorMode,
+    ensure_trailing_newline: bool,
+    hyperlink_paths: bool,
+    max_output_bytes: Option<usize>,
+    write_empty_files: bool,
+    routes: HashMap<ConsoleLogKind, OutputTarget>,
+    show_timestamps: bool,
+    timestamp_format: String,
+}
+
 pub struct Console {
     lang: String,
     log_list: Vec<ConsoleLog>,
     log_limit: ConsoleLogLimit,
+    suppressors: Vec<LogSuppressor>,
+    suppressed_log_count: usize,
     pub ignore_logs: bool,
+    pub label: Option<String>,
+    pub highlight_carets: bool,
+    pub compact: bool,
+    // note: (種類, 言語) ごとのラベルの上書き
+    pub kind_labels: HashMap<(ConsoleLogKind, String), String>,
+    // note: この種類より深刻度の低いログは出力時に表示しない
+    pub min_kind: ConsoleLogKind,
+    pre_quiet_min_kind: Option<ConsoleLogKind>,
+    pub show_source_loc: bool,
+    // note: None の場合はログファイルのヘッダを出力しない
+    pub log_file_header: Option<String>,
+    pub log_file_timestamp: bool,
+    pub color_depth: ColorDepth,
+    // note: 端末出力で表示する説明の最大数
+    pub max_descs_per_log: Option<usize>,
+    pub truncate_descs_in_files: bool,
+    pub color_mode: ColorMode,
+    pub ensure_trailing_newline: bool,
+    // note: 最初のエラーが追加された際に一度だけ通知する
+    pub alert_on_error: bool,
+    error_alerted: bool,
+    pub hyperlink_paths: bool,
+    // note: 出力全体のバイト数の上限
+    pub max_output_bytes: Option<usize>,
+    // note: false の場合はログのないログファイルを作成しない
+    pub write_empty_files: bool,
+    // note: 実行時に登録された言語ごとの (キー, メッセージ) の一覧
+    translations: HashMap<String, Vec<(String, String)>>,
+    // note: タイムスタンプを生成する際に参照する現在時刻の取得元
+    pub clock: Box<dyn Fn() -> DateTime<Local> + Send>,
+    // note: 種類ごとの出力先で, 未設定の種類は標準出力へ出力する
+    // ログファイルへの出力には影響せず, LogFile には常にすべてのログを書き込む
+    pub routes: HashMap<ConsoleLogKind, OutputTarget>,
+    // note: true の場合は try_append_log がエラーの追加時に Err を返す
+    pub fail_fast: bool,
+    // note: true の場合は各ログの先頭に timestamp_format で整形した現在時刻を出力する
+    pub show_timestamps: bool,
+    // note: show_timestamps が有効な場合に使う chrono の書式で, ログファイルのヘッダには影響しない
+    // 出力時に失敗しないよう set_timestamp_format で検証してから設定する
+    timestamp_format: String,
 }
 
 impl Console {
     pub fn new(lang: String, log_limit: ConsoleLogLimit) -> Console {
+        let log_file_header = Console::format_default_log_file_header(&lang);
+
         return Console {
             lang: lang,
             log_list: Vec::new(),
             log_limit: log_limit,
+            suppressors: Vec::new(),
+            suppressed_log_count: 0,
             ignore_logs: false,
+            label: None,
+            highlight_carets: false,
+            compact: false,
+            kind_labels: HashMap::new(),
+            min_kind: ConsoleLogKind::Note,
+            pre_quiet_min_kind: None,
+            show_source_loc: false,
+            log_file_header: Some(log_file_header),
+            log_file_timestamp: true,
+            color_depth: ColorDepth::Ansi16,
+            max_descs_per_log: None,
+            truncate_descs_in_files: false,
+            color_mode: ColorMode::Auto,
+            ensure_trailing_newline: false,
+            alert_on_error: false,
+            error_alerted: false,
+            hyperlink_paths: false,
+            max_output_bytes: None,
+            write_empty_files: true,
+            translations: HashMap::new(),
+            clock: Box::new(Local::now),
+            routes: HashMap::new(),
+            fail_fast: false,
+            show_timestamps: false,
+            timestamp_format: "%H:%M:%S".to_string(),
         };
     }
 
+    // note: 事前確保はメモリ上のログバッファにのみ影響する
+    pub fn with_capacity(lang: String, log_limit: ConsoleLogLimit, capacity: usize) -> Console {
+        let mut cons = Console::new(lang, log_limit);
+        cons.reserve(capacity);
+        return cons;
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.log_list.reserve(additional);
+    }
+
     pub fn get_lang(&self) -> &str {
         return &self.lang;
     }
 
-    pub fn append_log(&mut self, log: ConsoleLog) {
-        if !self.ignore_logs {
-            self.log_list.push(log);
-        }
-    }
+    // note: 判定できない場合は 16 色として扱う
+    pub fn detect_color_depth(&mut self) {
+        let is_truecolor_value = |v: &str| v == "truecolor" || v == "24bit";
 
-    pub fn clear(&mut self) {
-        self.log_list.clear();
+        let colorterm_supports_truecolor = match env::var("COLORTERM") {
+            Ok(v) => is_truecolor_value(&v.to_lowercase()),
+            Err(_) => false,
+        };
+
+        let term_supports_truecolor = match env::var("TERM") {
+            Ok(v) => v.ends_with("-direct") || v.ends_with("-truecolor"),
+            Err(_) => false,
+        };
+
+        self.color_depth = if colorterm_supports_truecolor || term_supports_truecolor {
+            ColorDepth::TrueColor
+        } else {
+            ColorDepth::Ansi16
+        };
     }
 
-    pub fn pop_log(&mut self) {
-        if self.log_list.len() > 0 {
-            self.log_list.pop();
-        }
+    // note: Auto の場合は標準出力が端末かどうかで判定する
+    pub fn is_color_enabled(&self) -> bool {
+        return match self.color_mode {
+            ColorMode::Auto => std::io::stdout().is_terminal(),
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+        };
     }
 
-    pub fn output(&self, log_files: Vec<LogFile>) {
-        let mut cons_log_lines = Vec::<String>::new();
-        self.print_all(&mut cons_log_lines);
+    fn get_color_escape(&self, kind: &ConsoleLogKind) -> String {
+        return match (self.color_depth, kind.get_log_color_rgb()) {
+            (ColorDepth::TrueColor, Some((r, g, b))) => format!("\x1b[38;2;{};{};{}m", r, g, b),
+            _ => format!("\x1b[{}m", kind.get_log_color_num()),
+        };
+    }
 
-        match self.write_all(log_files, cons_log_lines) {
-            Ok(()) => (),
-            Err(_) => println!("{}", Console::format_log_file_writing_failure_log()),
+    pub fn save_config(&self) -> ConsoleConfig {
+        return ConsoleConfig {
+            log_limit: self.log_limit.clone(),
+            label: self.label.clone(),
+            highlight_carets: self.highlight_carets,
+            compact: self.compact,
+            kind_labels: self.kind_labels.clone(),
+            min_kind: self.min_kind.clone(),
+            pre_quiet_min_kind: self.pre_quiet_min_kind.clone(),
+            show_source_loc: self.show_source_loc,
+            log_file_header: self.log_file_header.clone(),
+            log_file_timestamp: self.log_file_timestamp,
+            color_depth: self.color_depth,
+            max_descs_per_log: self.max_descs_per_log,
+            truncate_descs_in_files: self.truncate_descs_in_files,
+            color_mode: self.color_mode,
+            ensure_trailing_newline: self.ensure_trailing_newline,
+            hyperlink_paths: self.hyperlink_paths,
+            max_output_bytes: self.max_output_bytes,
+            write_empty_files: self.write_empty_files,
+            routes: self.routes.clone(),
+            show_timestamps: self.show_timestamps,
+            timestamp_format: self.timestamp_format.clone(),
         };
     }
 
-    fn write_all(&self, log_files: Vec<LogFile>, cons_log_lines: Vec<String>) -> FileManResult<()> {
-        let header = vec![
-            "--- Log File ---",
-            "",
-            &format!(" * created at {}", Local::now()),
-            " * generated by cons-util",
-        ].join("\n");
+    pub fn restore_config(&mut self, config: ConsoleConfig) {
+        self.log_limit = config.log_limit;
+        self.label = config.label;
+        self.highlight_carets = config.highlight_carets;
+        self.compact = config.compact;
+        self.kind_labels = config.kind_labels;
+        self.min_kind = config.min_kind;
+        self.pre_quiet_min_kind = config.pre_quiet_min_kind;
+        self.show_source_loc = config.show_source_loc;
+        self.log_file_header = config.log_file_header;
+        self.log_file_timestamp = config.log_file_timestamp;
+        self.color_depth = config.color_depth;
+        self.max_descs_per_log = config.max_descs_per_log;
+        self.truncate_descs_in_files = config.truncate_descs_in_files;
+        self.color_mode = config.color_mode;
+        self.ensure_trailing_newline = config.ensure_trailing_newline;
+        self.hyperlink_paths = config.hyperlink_paths;
+        self.max_output_bytes = config.max_output_bytes;
+        self.write_empty_files = config.write_empty_files;
+        self.routes = config.routes;
+        self.show_timestamps = config.show_timestamps;
+        self.timestamp_format = config.timestamp_format;
+    }
 
-        for each_file_log in log_files {
-            let lines = match &each_file_log.kind {
-                LogFileKind::TextLines(lines) => lines,
-                LogFileKind::ConsoleLogs => &cons_log_lines,
-            };
+    // note: f の中で変更した設定は f の終了後に元に戻す
+    pub fn with_config<R>(&mut self, f: impl FnOnce(&mut Console) -> R) -> R {
+        let config = self.save_config();
+        let result = f(self);
+        self.restore_config(config);
+        return result;
+    }
 
-            let output_content = header.clone() + "\n\n" + &lines.join("\n");
+    pub fn get_timestamp_format(&self) -> &str {
+        return &self.timestamp_format;
+    }
 
-            // [fix] use write_lines()
-            FilePath::write(&FilePath::new(each_file_log.output_path), &output_content)?;
+    pub fn set_timestamp_format(&mut self, format: String) -> Result<(), InternalLog> {
+        if StrftimeItems::new(&format).any(|each_item| each_item == Item::Error) {
+            return Err(InternalLog::InvalidTimestampFormat { format: format });
         }
 
+        self.timestamp_format = format;
         return Ok(());
     }
 
-    fn print_all(&self, log_lines: &mut Vec<String>) {
-        // note: ログ数制限のチェック
-        let limit_num = match &self.log_limit {
-            ConsoleLogLimit::NoLimit => -1i32,
-            ConsoleLogLimit::Limited(v) => *v as i32,
+    pub fn get_kind_label(&self, kind: &ConsoleLogKind) -> String {
+        return match self.kind_labels.get(&(kind.clone(), self.lang.clone())) {
+            Some(v) => v.clone(),
+            None => kind.get_log_kind_name(&self.lang),
         };
+    }
 
-        let mut log_count = 0;
+    // note: 同じキーが複数回登録された場合は最初のものを使う
+    pub fn register_translations(&mut self, lang: &str, entries: Vec<(String, String)>) {
+        self.translations.entry(lang.to_string()).or_default().extend(entries);
+    }
 
-        for each_log in &self.log_list {
-            if limit_num != -1 && log_count + 1 > limit_num as i32 {
-                self.print(&InternalLog::LogLimitExceeded { log_limit: self.log_limit.clone() }.translate(&self.lang), &mut Vec::new());
-                break;
+    // note: 現在の言語で見つからない場合は en のメッセージを使う
+    pub fn get_translation(&self, key: &str) -> Option<String> {
+        for each_lang in [self.lang.as_str(), "en"] {
+            let found = self.translations.get(each_lang).and_then(|entries| entries.iter().find(|(each_key, _)| each_key == key));
+
+            if let Some((_, msg)) = found {
+                return Some(msg.clone());
+            }
+        }
+
+        return None;
+    }
+
+    // note: 同じキーが異なるメッセージで登録されている箇所ごとに警告を追加する
+    pub fn validate_translations(&mut self) -> ConsoleResult<()> {
+        let mut langs: Vec<&String> = self.translations.keys().collect();
+        langs.sort();
+
+        let mut collision_logs = Vec::<ConsoleLog>::new();
+
+        for each_lang in langs {
+            let mut first_msgs = HashMap::<&str, &str>::new();
+            let mut collided_keys = Vec::<&str>::new();
+
+            for (each_key, each_msg) in &self.translations[each_lang] {
+                match first_msgs.get(each_key.as_str()) {
+                    Some(first_msg) if *first_msg != each_msg && !collided_keys.contains(&each_key.as_str()) => {
+                        collided_keys.push(each_key);
+                        collision_logs.push(InternalLog::TranslationKeyCollision { key: each_key.clone(), language: each_lang.clone() }.translate(&self.lang));
+                    },
+                    Some(_) => (),
+                    None => {
+                        first_msgs.insert(each_key, each_msg);
+                    },
+                }
             }
+        }
 
-            self.print(each_log, log_lines);
-            log_count += 1;
+        let has_collisions = !collision_logs.is_empty();
+
+        for each_log in collision_logs {
+            self.append_log(each_log);
         }
+
+        return if has_collisions {
+            Err(())
+        } else {
+            Ok(())
+        };
+    }
+
+    pub fn get_suppressed_log_count(&self) -> usize {
+        return self.suppressed_log_count;
     }
 
-    fn print(&self, log: &ConsoleLog, log_lines: &mut Vec<String>) {
-        let kind_color = log.kind.get_log_color_num();
-        let kind_name = &log.kind.get_log_kind_name();
-        let msg = &log.msg;
+    // note: predicate が true を返したログは append_log で破棄される
+    pub fn suppress(&mut self, predicate: LogSuppressor) {
+        self.suppressors.push(predicate);
+    }
 
-        println!("{}", Console::format_title(Some(kind_color), kind_name, msg));
-        log_lines.push(Console::format_title(None, kind_name, msg));
+    // note: 有効時はエラーのみを表示し, 無効にすると元の表示基準に戻す
+    pub fn quiet(&mut self, enabled: bool) {
+        if enabled {
+            if self.pre_quiet_min_kind.is_none() {
+                self.pre_quiet_min_kind = Some(self.min_kind.clone());
+            }
 
-        println!();
-        log_lines.push(String::new());
+            self.min_kind = ConsoleLogKind::Error;
+        } else {
+            if let Some(v) = self.pre_quiet_min_kind.take() {
+                self.min_kind = v;
+            }
+        }
     }
 
-    fn format_log_file_writing_failure_log() -> String {
-        let err_log_kind = ConsoleLogKind::Error;
-        return Console::format_title(Some(err_log_kind.get_log_color_num()), &err_log_kind.get_log_kind_name(), "log file writing failure");
+    // note: 0 はエラーと警告, 1 以上は注記も表示する
+    pub fn set_verbosity(&mut self, level: u8) {
+        self.min_kind = match level {
+            0 => ConsoleLogKind::Warning,
+            _ => ConsoleLogKind::Note,
+        };
     }
 
-    fn format_title(color: Option<usize>, kind: &str, title: &str) -> String {
-        let (color_begin, color_end) = match color {
-            Some(v) => (format!("\x1b[{}m", v), "\x1b[m".to_string()),
-            None => (String::new(), String::new()),
+    pub fn append_log(&mut self, log: ConsoleLog) {
+        self.push_log(log);
+    }
+
+    // note: append_log と同様にログを追加し, fail_fast が有効でエラーを追加した場合は Err を返す
+    // ignore_logs や suppress によって破棄されたエラーでは Err を返さない
+    pub fn try_append_log(&mut self, log: ConsoleLog) -> ConsoleResult<()> {
+        let is_error = log.kind.is_error();
+        let is_appended = self.push_log(log);
+
+        return if self.fail_fast && is_error && is_appended {
+            Err(())
+        } else {
+            Ok(())
         };
+    }
 
-        return format!("{}[{}]{} {}", color_begin, kind, color_end, title);
+    // note: 区切り行は suppress の対象にしない
+    pub fn append_separator(&mut self) {
+        if !self.ignore_logs {
+            self.log_list.push(ConsoleLog::separator());
+        }
+    }
+
+    // note: ログを追加した場合は true, 破棄した場合は false を返す
+    fn push_log(&mut self, log: ConsoleLog) -> bool {
+        if self.ignore_logs {
+            return false;
+        }
+
+        if self.suppressors.iter().any(|each_suppressor| each_suppressor(&log)) {
+            self.suppressed_log_count += 1;
+            return false;
+        }
+
+        if self.alert_on_error && !self.error_alerted && log.kind.is_error() {
+            self.error_alerted = true;
+            Console::alert_error(&log);
+        }
+
+        self.log_list.push(log);
+        return true;
+    }
+
+    // note: 区切り行を除いたログを返す
+    fn get_logs(&self) -> impl Iterator<Item = &ConsoleLog> {
+        return self.log_list.iter().filter(|each_log| !each_log.is_separator);
+    }
+
+    fn alert_error(log: &ConsoleLog) {
+        // note: 端末でない場合はベルを鳴らさない
+        if std::io::stdout().is_terminal() {
+            print!("\x07");
+            let _ = std::io::stdout().flush();
+        }
+
+        #[cfg(feature = "notify")]
+        {
+            let _ = notify_rust::Notification::new().summary("cons-util").body(log.get_title()).show();
+        }
+
+        #[cfg(not(feature = "notify"))]
+        let _ = log;
+    }
+
+    // note: 翻訳済みのログを移すため, 出力時の言語は other に依存しない
+    pub fn absorb(&mut self, other: Console) {
+        for each_log in other.log_list {
+            self.append_log(each_log);
+        }
+    }
+
+    // note: ログは翻訳済みの文字列として保持しているため, そのまま複製できる
+    pub fn filtered(&self, predicate: impl Fn(&ConsoleLog) -> bool) -> Console {
+        let mut filtered_cons = Console::new(self.lang.clone(), self.log_limit.clone());
+        filtered_cons.log_list = self.log_list.iter().filter(|each_log| each_log.is_separator || predicate(each_log)).cloned().collect();
+        return filtered_cons;
+    }
+
+    pub fn clear(&mut self) {
+        self.log_list.clear();
+    }
+
+    pub fn pop_log(&mut self) {
+        if self.log_list.len() > 0 {
+            self.log_list.pop();
+        }
+    }
+
+    // note: predicate が false を返したログを破棄する
+    pub fn retain(&mut self, predicate: impl Fn(&ConsoleLog) -> bool) {
+        self.log_list.retain(|each_log| each_log.is_separator || predicate(each_log));
+    }
+
+    // note: predicate には翻訳済みのタイトルを渡す
+    pub fn retain_translated(&mut self, predicate: impl Fn(&str) -> bool) {
+        self.log_list.retain(|each_log| each_log.is_separator || predicate(each_log.get_title()));
+    }
+
+    pub fn checkpoint(&mut self) -> usize {
+        return self.log_list.len();
+    }
+
+    // note: checkpoint() 以降に追加されたログを破棄する
+    pub fn rollback_to(&mut self, checkpoint: usize) {
+        self.log_list.truncate(checkpoint);
+    }
+
+    // note: 組み込みの種類は has_errors と同様に深刻度で数えるため, 同じ深刻度の独自の種類も含む
+    // 独自の種類を指定した場合はその種類のログのみを数える
+    pub fn count_logs(&self, kind: &ConsoleLogKind) -> usize {
+        return self.get_logs().filter(|each_log| match kind {
+            ConsoleLogKind::Error => each_log.kind.is_error(),
+            ConsoleLogKind::Warning => each_log.kind.is_warning(),
+            ConsoleLogKind::Note => !each_log.kind.is_error() && !each_log.kind.is_warning(),
+            ConsoleLogKind::Custom { .. } => each_log.kind == *kind,
+        }).count();
+    }
+
+    pub fn has_errors(&self) -> bool {
+        return self.get_logs().any(|each_log| each_log.kind.is_error());
+    }
+
+    pub fn bail_if_errors(&self) -> ConsoleResult<()> {
+        return if self.has_errors() {
+            Err(())
+        } else {
+            Ok(())
+        };
+    }
+
+    pub fn into_exit_code(self) -> ExitCode {
+        return if self.has_errors() {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        };
+    }
+
+    // note: ロガー自体の調査用にログを翻訳せず設定と件数のみをまとめる
+    pub fn dump_debug(&self) -> String {
+        let kind_counts: Vec<String> = ConsoleLogKind::all().iter()
+            .map(|each_kind| format!("{}={}", each_kind, self.count_logs(each_kind)))
+            .collect();
+
+        return [
+            format!("lang: {}", self.lang),
+            format!("log_limit: {}", self.log_limit),
+            format!("ignore_logs: {}", self.ignore_logs),
+            format!("log_count: {} ({})", self.get_logs().count(), kind_counts.join(", ")),
+            format!("min_kind: {}", self.min_kind),
+            format!("quiet: {}", self.pre_quiet_min_kind.is_some()),
+            format!("suppressors: {}", self.suppressors.len()),
+            format!("suppressed_log_count: {}", self.suppressed_log_count),
+        ].join("\n");
+    }
+
+    pub fn output(&self, log_files: Vec<LogFile>) {
+        if self.routes.is_empty() {
+            self.render(&mut std::io::stdout(), self.is_color_enabled());
+        } else if self.output_routed().is_err() {
+            println!("{}", self.format_log_file_writing_failure_log());
+        }
+
+        match self.write_log_files(log_files) {
+            Ok(_) => (),
+            Err(_) => println!("{}", self.format_log_file_writing_failure_log()),
+        };
+    }
+
+    // note: key が返す値ごとにログをまとめ, 値を見出しとして出力する
+    // グループは最初に現れた順に並べ, key が None を返したログは最後にまとめる
+    pub fn output_grouped_by<F: Fn(&ConsoleLog) -> Option<String>>(&self, key: F, writer: &mut dyn Write) -> ConsoleResult<()> {
+        let (shown_logs, _) = self.get_shown_logs();
+        let mut groups = Vec::<(String, Vec<&ConsoleLog>)>::new();
+        let mut ungrouped_logs = Vec::<&ConsoleLog>::new();
+
+        // note: 区切り行はグループの見出しで代替されるため出力しない
+        for each_log in shown_logs.into_iter().filter(|each_log| !each_log.is_separator) {
+            let group_key = match key(each_log) {
+                Some(v) => v,
+                None => {
+                    ungrouped_logs.push(each_log);
+                    continue;
+                },
+            };
+
+            match groups.iter_mut().find(|(each_key, _)| *each_key == group_key) {
+                Some((_, logs)) => logs.push(each_log),
+                None => groups.push((group_key, vec![each_log])),
+            }
+        }
+
+        if !ungrouped_logs.is_empty() {
+            groups.push((InternalLog::UngroupedLogs.translate(&self.lang).msg, ungrouped_logs));
+        }
+
+        for (each_key, each_logs) in groups {
+            match writeln!(writer, "--> {}\n", each_key) {
+                Ok(()) => (),
+                Err(_) => return Err(()),
+            }
+
+            for each_log in each_logs {
+                Console::write_rendered_text(writer, &self.format_rendered_log(each_log, false, true))?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    // note: スクリプトから扱えるよう "errors=3 warnings=1 notes=0" の形式で出力する
+    // 翻訳せず, 形式を変更しないこと
+    pub fn print_counts(&self, writer: &mut dyn Write) -> ConsoleResult<()> {
+        let counts: Vec<String> = ConsoleLogKind::all().iter()
+            .map(|each_kind| format!("{}s={}", each_kind, self.count_logs(each_kind)))
+            .collect();
+
+        return match writeln!(writer, "{}", counts.join(" ")) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(()),
+        };
+    }
+
+    pub fn output_json(&self, writer: &mut dyn Write) -> ConsoleResult<()> {
+        let log_objs: Vec<String> = self.get_logs().map(Console::format_json_log).collect();
+        let json = format!("{{\"version\":{},\"logs\":[{}]}}", JSON_SCHEMA_VERSION, log_objs.join(","));
+
+        return match writeln!(writer, "{}", json) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(()),
+        };
+    }
+
+    // note: ログを 1 行ずつ JSON オブジェクトとして出力する
+    pub fn output_ndjson(&self, writer: &mut dyn Write) -> ConsoleResult<()> {
+        return match writer.write_all(self.format_ndjson().as_bytes()) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(()),
+        };
+    }
+
+    fn format_ndjson(&self) -> String {
+        let lines: Vec<String> = self.get_logs().map(|each_log| format!("{}\n", Console::format_json_log(each_log))).collect();
+        return lines.concat();
+    }
+
+    fn format_json_log(log: &ConsoleLog) -> String {
+        let descs: Vec<String> = log.get_descs().iter().map(|each_desc| format!("\"{}\"", Console::escape_json_str(each_desc))).collect();
+
+        // note: 色番号は機械向けの出力に含めず, 独自の種類は名前のみを出力する
+        let kind = match &log.kind {
+            ConsoleLogKind::Custom { name, .. } => name.clone(),
+            _ => log.kind.to_string(),
+        };
+
+        let code = match &log.code {
+            Some(v) => format!("\"{}\"", Console::escape_json_str(v)),
+            None => "null".to_string(),
+        };
+
+        let source_loc = match &log.source_loc {
+            Some(v) => format!(
+                "{{\"file\":\"{}\",\"line\":{},\"column\":{}}}",
+                Console::escape_json_str(v.file),
+                v.line,
+                v.column,
+            ),
+            None => "null".to_string(),
+        };
+
+        return format!(
+            "{{\"kind\":\"{}\",\"code\":{},\"source_loc\":{},\"title\":\"{}\",\"descs\":[{}]}}",
+            Console::escape_json_str(&kind),
+            code,
+            source_loc,
+            Console::escape_json_str(log.get_title()),
+            descs.join(","),
+        );
+    }
+
+    fn escape_json_str(s: &str) -> String {
+        let mut escaped = String::new();
+
+        for each_char in s.chars() {
+            match each_char {
+                '"' => escaped.push_str("\\\""),
+                '\\' => escaped.push_str("\\\\"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '\t' => escaped.push_str("\\t"),
+                c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+                c => escaped.push(c),
+            }
+        }
+
+        return escaped;
+    }
+
+    pub fn write_log_files(&self, log_files: Vec<LogFile>) -> FileManResult<Vec<(String, u64)>> {
+        return self.write_all(log_files);
+    }
+
+    // note: GitHub Actions のワークフローコマンドとして出力する
+    // source_loc はログを生成したツール側のソースの位置であり, 診断対象のファイルではないため file= には使わない
+    pub fn output_github_actions(&self, writer: &mut dyn Write) -> ConsoleResult<()> {
+        for each_log in self.get_logs() {
+            let command_name = if each_log.kind.is_error() {
+                "error"
+            } else if each_log.kind.is_warning() {
+                "warning"
+            } else {
+                "notice"
+            };
+
+            let line = format!("::{}::{}", command_name, Console::escape_github_actions_data(&each_log.msg));
+
+            match writeln!(writer, "{}", line) {
+                Ok(()) => (),
+                Err(_) => return Err(()),
+            }
+        }
+
+        return Ok(());
+    }
+
+    fn escape_github_actions_data(s: &str) -> String {
+        return s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A");
+    }
+
+    // note: 削除行はエラーと同じ色, 追加行は緑で表示する
+    #[cfg(feature = "diff")]
+    pub fn print_diff(&self, diff: &[DiffLine]) {
+        let (added_color, removed_color, color_end) = if self.is_color_enabled() {
+            let added_color = match self.color_depth {
+                ColorDepth::Ansi16 => "\x1b[32m".to_string(),
+                ColorDepth::TrueColor => "\x1b[38;2;85;200;85m".to_string(),
+            };
+
+            (added_color, self.get_color_escape(&ConsoleLogKind::Error), "\x1b[m")
+        } else {
+            (String::new(), String::new(), "")
+        };
+
+        for each_line in diff {
+            match each_line {
+                DiffLine::Added(v) => println!("{}+ {}{}", added_color, v, color_end),
+                DiffLine::Removed(v) => println!("{}- {}{}", removed_color, v, color_end),
+                DiffLine::Unchanged(v) => println!("  {}", v),
+            }
+        }
+    }
+
+    #[cfg(feature = "syslog")]
+    pub fn output_syslog(&self, facility: syslog::Facility) -> Result<(), InternalLog> {
+        let process_name = match &self.label {
+            Some(v) => v.clone(),
+            None => "cons-util".to_string(),
+        };
+
+        let formatter = syslog::Formatter3164 {
+            facility: facility,
+            hostname: None,
+            process: process_name,
+            pid: std::process::id(),
+        };
+
+        let mut logger = match syslog::unix(formatter) {
+            Ok(v) => v,
+            Err(_) => return Err(InternalLog::FailedToConnectToSyslog),
+        };
+
+        for each_log in self.get_logs() {
+            // note: syslog のメッセージは 1 行にまとめる
+            let msg = each_log.msg.replace("\n\t", " / ");
+
+            let result = if each_log.kind.is_error() {
+                logger.err(msg)
+            } else if each_log.kind.is_warning() {
+                logger.warning(msg)
+            } else {
+                logger.notice(msg)
+            };
+
+            if result.is_err() {
+                return Err(InternalLog::FailedToWriteToSyslog);
+            }
+        }
+
+        return Ok(());
+    }
+
+    // note: 書き込んだファイルのパスとバイト数を返す
+    fn write_all(&self, log_files: Vec<LogFile>) -> FileManResult<Vec<(String, u64)>> {
+        let header = self.format_log_file_header();
+        let mut written_files = Vec::<(String, u64)>::new();
+
+        for each_file_log in log_files {
+            let is_empty = match &each_file_log.kind {
+                LogFileKind::TextLines(lines) => lines.is_empty(),
+                LogFileKind::ConsoleLogs => !self.has_file_logs(),
+                LogFileKind::Ndjson | LogFileKind::Replayable => self.get_logs().next().is_none(),
+            };
+
+            if is_empty && !self.write_empty_files {
+                continue;
+            }
+
+            let mut output_content = match &each_file_log.kind {
+                LogFileKind::TextLines(lines) => Console::join_log_file_content(&header, lines),
+                LogFileKind::ConsoleLogs => {
+                    let mut buf = Vec::<u8>::new();
+
+                    if self.write_console_log_file(&mut buf).is_err() {
+                        return Err(FileManLog::FailedToWriteFile { path: each_file_log.output_path });
+                    }
+
+                    String::from_utf8_lossy(&buf).to_string()
+                },
+                // note: 追記しても壊れないようにヘッダは付けない
+                LogFileKind::Ndjson => self.format_ndjson(),
+                // note: 読み戻せるようにヘッダは付けない
+                LogFileKind::Replayable => self.format_replay(),
+            };
+
+            if self.ensure_trailing_newline && !output_content.ends_with('\n') {
+                output_content.push('\n');
+            }
+
+            // [fix] use write_lines()
+            FilePath::write(&FilePath::new(each_file_log.output_path.clone()), &output_content)?;
+            written_files.push((each_file_log.output_path, output_content.len() as u64));
+        }
+
+        return Ok(written_files);
+    }
+
+    // note: ConsoleLogs 形式のログファイルの内容を出力する
+    // write_log_files と stream_to_file で同じ内容になるよう, どちらもこれを使う
+    fn write_console_log_file(&self, writer: &mut dyn Write) -> ConsoleResult<()> {
+        let mut ends_with_newline = false;
+
+        if let Some(v) = self.format_log_file_header() {
+            Console::write_rendered_text(writer, &format!("{}\n\n", v))?;
+            ends_with_newline = true;
+        }
+
+        let mut has_pending_newline = false;
+        let mut rendered_count = 0;
+
+        // note: 行ごとに改行で連結した場合と同様に, 最後のログの末尾の改行は出力しない
+        self.render_logs_with(false, false, &mut rendered_count, &mut |_, text| {
+            if has_pending_newline {
+                Console::write_rendered_text(writer, "\n")?;
+            }
+
+            let body = match text.strip_suffix('\n') {
+                Some(v) => v,
+                None => text,
+            };
+
+            Console::write_rendered_text(writer, body)?;
+            has_pending_newline = true;
+            ends_with_newline = body.ends_with('\n') || (body.is_empty() && ends_with_newline);
+            return Ok(());
+        })?;
+
+        if self.ensure_trailing_newline && !ends_with_newline {
+            Console::write_rendered_text(writer, "\n")?;
+        }
+
+        return Ok(());
+    }
+
+    fn has_file_logs(&self) -> bool {
+        let (shown_logs, limit_exceeded) = self.get_shown_logs();
+        return !shown_logs.is_empty() || limit_exceeded;
+    }
+
+    fn join_log_file_content(header: &Option<String>, lines: &[String]) -> String {
+        return match header {
+            Some(v) => v.clone() + "\n\n" + &lines.join("\n"),
+            None => lines.join("\n"),
+        };
+    }
+
+    // note: 1 行につき 1 ログをタブ区切りで出力する
+    // 翻訳キーを持つログは "種類 key キー 名前=値..." , 持たないログは "種類 msg メッセージ" の形式にする
+    fn format_replay(&self) -> String {
+        let lines: Vec<String> = self.get_logs().map(|each_log| {
+            let columns = match &each_log.replay {
+                Some(v) => {
+                    let fields = v.fields.iter().map(|(each_name, each_value)| format!("{}={}", each_name, Console::escape_replay_str(each_value)));
+                    ["key".to_string(), v.key.clone()].into_iter().chain(fields).collect::<Vec<String>>()
+                },
+                None => vec!["msg".to_string(), Console::escape_replay_str(&each_log.msg)],
+            };
+
+            return format!("{}\t{}\n", each_log.kind, columns.join("\t"));
+        }).collect();
+
+        return lines.concat();
+    }
+
+    // note: 翻訳キーを持つログは register_translations で登録されたメッセージから現在の言語で組み立て直す
+    pub fn load_replay(&self, path: &FilePath) -> FileManResult<Vec<ConsoleLog>> {
+        let parse_err = || FileManLog::FailedToParseFile { path: path.to_string() };
+        let mut logs = Vec::<ConsoleLog>::new();
+
+        for each_line in path.read_lines()? {
+            if each_line.is_empty() {
+                continue;
+            }
+
+            let columns: Vec<&str> = each_line.split('\t').collect();
+
+            let kind = match columns[0].parse::<ConsoleLogKind>() {
+                Ok(v) => v,
+                Err(_) => return Err(parse_err()),
+            };
+
+            let log = match &columns[1..] {
+                ["msg", escaped_msg] => ConsoleLog::new(kind, Console::unescape_replay_str(escaped_msg)),
+                ["key", key, field_columns @ ..] => {
+                    let mut fields = Vec::<(String, String)>::new();
+
+                    for each_column in field_columns {
+                        match each_column.split_once('=') {
+                            Some((name, escaped_value)) => fields.push((name.to_string(), Console::unescape_replay_str(escaped_value))),
+                            None => return Err(parse_err()),
+                        }
+                    }
+
+                    let template = match self.get_translation(key) {
+                        Some(v) => v,
+                        None => return Err(parse_err()),
+                    };
+
+                    let replay = LogReplay { key: key.to_string(), fields: fields };
+                    ConsoleLog::new(kind, Console::fill_replay_template(&template, &replay.fields)).with_replay(replay)
+                },
+                _ => return Err(parse_err()),
+            };
+
+            logs.push(log);
+        }
+
+        return Ok(logs);
+    }
+
+    // note: "{名前}" をフィールドの値に置き換え, "{{" と "}}" は format! と同様に扱う
+    fn fill_replay_template(template: &str, fields: &[(String, String)]) -> String {
+        let mut msg = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(each_char) = chars.next() {
+            match each_char {
+                '{' | '}' if chars.peek() == Some(&each_char) => {
+                    chars.next();
+                    msg.push(each_char);
+                },
+                '{' => {
+                    let name: String = chars.by_ref().take_while(|c| *c != '}').collect();
+
+                    match fields.iter().find(|(each_name, _)| *each_name == name) {
+                        Some((_, value)) => msg.push_str(value),
+                        None => msg.push_str(&format!("{{{}}}", name)),
+                    }
+                },
+                c => msg.push(c),
+            }
+        }
+
+        return msg;
+    }
+
+    fn escape_replay_str(s: &str) -> String {
+        return s.replace('\\', "\\\\").replace('\n', "\\n").replace('\t', "\\t");
+    }
+
+    fn unescape_replay_str(escaped: &str) -> String {
+        let mut s = String::new();
+        let mut chars = escaped.chars();
+
+        while let Some(each_char) = chars.next() {
+            if each_char != '\\' {
+                s.push(each_char);
+                continue;
+            }
+
+            match chars.next() {
+                Some('n') => s.push('\n'),
+                Some('t') => s.push('\t'),
+                Some(c) => s.push(c),
+                None => s.push('\\'),
+            }
+        }
+
+        return s;
+    }
+
+    // note: ログ行をメモリ上に溜めず, 1 ログずつファイルへ書き込む
+    pub fn stream_to_file(&self, path: &FilePath) -> FileManResult<()> {
+        if !self.has_file_logs() && !self.write_empty_files {
+            return Ok(());
+        }
+
+        let mut writer = BufWriter::new(path.create_file()?);
+
+        if self.write_console_log_file(&mut writer).is_err() {
+            return Err(FileManLog::FailedToWriteFile { path: path.to_string() });
+        }
+
+        return match writer.flush() {
+            Ok(()) => Ok(()),
+            Err(_) => Err(FileManLog::FailedToWriteFile { path: path.to_string() }),
+        };
+    }
+
+    pub fn format_default_log_file_header(lang: &str) -> String {
+        return format!(
+            "{}\n\n{}",
+            InternalLog::LogFileHeader.translate(lang).msg,
+            InternalLog::LogFileGeneratedBy.translate(lang).msg,
+        );
+    }
+
+    fn format_log_file_header(&self) -> Option<String> {
+        let header = match &self.log_file_header {
+            Some(v) => v.clone(),
+            None => return None,
+        };
+
+        return if self.log_file_timestamp {
+            Some(format!("{}\n{}", header, InternalLog::LogFileCreatedAt { time: (self.clock)().to_string() }.translate(&self.lang).msg))
+        } else {
+            Some(header)
+        };
+    }
+
+    // note: 表示対象のログと, ログ数制限を超過したかどうかを返す
+    fn get_shown_logs(&self) -> (Vec<&ConsoleLog>, bool) {
+        let limit_num = match &self.log_limit {
+            ConsoleLogLimit::NoLimit => None,
+            ConsoleLogLimit::Limited(v) => Some(*v),
+        };
+
+        let mut shown_logs = Vec::<&ConsoleLog>::new();
+        let mut counted_log_count = 0;
+
+        for each_log in &self.log_list {
+            // note: 区切り行は絞り込みとログ数制限の対象外
+            if each_log.is_separator {
+                shown_logs.push(each_log);
+                continue;
+            }
+
+            if each_log.kind.get_severity() < self.min_kind.get_severity() {
+                continue;
+            }
+
+            // note: ログ数制限のチェック
+            match limit_num {
+                Some(v) if counted_log_count + 1 > v => return (shown_logs, true),
+                _ => (),
+            }
+
+            shown_logs.push(each_log);
+            counted_log_count += 1;
+        }
+
+        return (shown_logs, false);
+    }
+
+    // note: 表示対象のログを writer へ出力し, 出力したログの数を返す
+    // colorize は ColorMode による判定より優先される
+    pub fn render(&self, writer: &mut dyn Write, colorize: bool) -> usize {
+        let mut rendered_count = 0;
+        let _ = self.render_logs(writer, colorize, true, &mut rendered_count);
+        return rendered_count;
+    }
+
+    // note: 端末出力と同じ内容を色なしで文字列として返す
+    pub fn render_to_string(&self) -> String {
+        let mut buf = Vec::<u8>::new();
+        self.render(&mut buf, false);
+        return String::from_utf8_lossy(&buf).to_string();
+    }
+
+    // note: for_terminal が false の場合はログファイル向けに出力する
+    fn render_logs(&self, writer: &mut dyn Write, colorize: bool, for_terminal: bool, rendered_count: &mut usize) -> ConsoleResult<()> {
+        return self.render_logs_with(colorize, for_terminal, rendered_count, &mut |_, text| Console::write_rendered_text(writer, text));
+    }
+
+    // note: 表示対象のログを整形し, ログと整形後の文字列を emit に渡す
+    // ログ数制限や出力打ち切りの通知も emit に渡される
+    fn render_logs_with(&self, colorize: bool, for_terminal: bool, rendered_count: &mut usize, emit: &mut dyn FnMut(&ConsoleLog, &str) -> ConsoleResult<()>) -> ConsoleResult<()> {
+        let (shown_logs, limit_exceeded) = self.get_shown_logs();
+        let mut output_bytes = 0;
+
+        for each_log in shown_logs {
+            let text = self.format_rendered_log(each_log, colorize, for_terminal);
+
+            // note: 出力バイト数の上限を超える場合は通知を出力して打ち切る
+            match self.max_output_bytes {
+                Some(v) if output_bytes + text.len() > v => {
+                    let truncated_log = InternalLog::OutputTruncated.translate(&self.lang);
+                    return emit(&truncated_log, &self.format_rendered_log(&truncated_log, colorize, for_terminal));
+                },
+                _ => (),
+            }
+
+            emit(each_log, &text)?;
+            output_bytes += text.len();
+
+            if !each_log.is_separator {
+                *rendered_count += 1;
+            }
+        }
+
+        // note: ログ数制限を超過した場合は (制限数 + 1) 件目として通知を出力する
+        if limit_exceeded {
+            let limit_log = InternalLog::LogLimitExceeded { log_limit: self.log_limit.clone() }.translate(&self.lang);
+            emit(&limit_log, &self.format_rendered_log(&limit_log, colorize, for_terminal))?;
+        }
+
+        return Ok(());
+    }
+
+    // note: routes に従ってログごとに出力先を切り替える
+    // ログ数制限や出力打ち切りの通知もその通知の種類の出力先へ出力する
+    fn output_routed(&self) -> FileManResult<()> {
+        let colorize = self.is_color_enabled();
+        let mut file_contents = Vec::<(String, String)>::new();
+        let mut rendered_count = 0;
+
+        let _ = self.render_logs_with(colorize, true, &mut rendered_count, &mut |log, text| {
+            let target = match self.routes.get(&log.kind) {
+                Some(v) => v.clone(),
+                None => OutputTarget::Stdout,
+            };
+
+            match target {
+                OutputTarget::Stdout => Console::write_rendered_text(&mut std::io::stdout(), text)?,
+                OutputTarget::Stderr => Console::write_rendered_text(&mut std::io::stderr(), text)?,
+                OutputTarget::File(path) => {
+                    // note: ファイルへは色やハイパーリンクなしで出力する
+                    let file_text = self.format_rendered_log(log, false, false);
+
+                    match file_contents.iter_mut().find(|(each_path, _)| *each_path == path) {
+                        Some((_, content)) => content.push_str(&file_text),
+                        None => file_contents.push((path, file_text)),
+                    }
+                },
+                OutputTarget::None => (),
+            }
+
+            return Ok(());
+        });
+
+        for (each_path, each_content) in file_contents {
+            FilePath::new(each_path).write(&each_content)?;
+        }
+
+        return Ok(());
+    }
+
+    fn write_rendered_text(writer: &mut dyn Write, text: &str) -> ConsoleResult<()> {
+        return match writer.write_all(text.as_bytes()) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(()),
+        };
+    }
+
+    fn format_rendered_log(&self, log: &ConsoleLog, colorize: bool, for_terminal: bool) -> String {
+        if log.is_separator {
+            return self.format_separator(for_terminal);
+        }
+
+        let kind_color = if colorize {
+            Some(self.get_color_escape(&log.kind))
+        } else {
+            None
+        };
+
+        let kind_name = &match &log.code {
+            Some(v) => format!("{} {}", self.get_kind_label(&log.kind), v),
+            None => self.get_kind_label(&log.kind),
+        };
+
+        let msg = self.format_msg(log, for_terminal);
+
+        let msg = match &kind_color {
+            Some(v) if self.highlight_carets => Console::highlight_caret_lines(&msg, v),
+            _ => msg,
+        };
+
+        // note: ハイパーリンクは色付きの端末出力でのみ有効にする
+        let msg = if self.hyperlink_paths && for_terminal && kind_color.is_some() {
+            Console::hyperlink_paths_in(&msg)
+        } else {
+            msg
+        };
+
+        let timestamp_prefix = if self.show_timestamps {
+            format!("{} ", (self.clock)().format(&self.timestamp_format))
+        } else {
+            String::new()
+        };
+
+        let mut text = format!("{}{}{}\n", timestamp_prefix, self.format_label_prefix(), Console::format_title(kind_color.as_deref(), kind_name, &msg));
+
+        // note: compact 時はログ間の空行を出力しない
+        if !self.compact {
+            text.push('\n');
+        }
+
+        return text;
+    }
+
+    // note: 端末では端末幅の罫線, ファイルではハイフンの行として出力する
+    fn format_separator(&self, for_terminal: bool) -> String {
+        let mut text = if for_terminal {
+            "─".repeat(Console::get_terminal_width())
+        } else {
+            "-".repeat(DEFAULT_TERMINAL_WIDTH)
+        };
+
+        text.push('\n');
+
+        if !self.compact {
+            text.push('\n');
+        }
+
+        return text;
+    }
+
+    // note: 端末幅は COLUMNS 環境変数から取得する
+    fn get_terminal_width() -> usize {
+        return match env::var("COLUMNS").ok().and_then(|v| v.parse::<usize>().ok()) {
+            Some(v) if v > 0 => v,
+            _ => DEFAULT_TERMINAL_WIDTH,
+        };
+    }
+
+    fn format_msg(&self, log: &ConsoleLog, for_terminal: bool) -> String {
+        let descs = log.get_descs();
+
+        let max_descs = match self.max_descs_per_log {
+            Some(v) if for_terminal || self.truncate_descs_in_files => v,
+            _ => descs.len(),
+        };
+
+        let mut msg = log.get_title().to_string();
+
+        for each_desc in descs.iter().take(max_descs) {
+            msg += "\n\t";
+            msg += each_desc;
+        }
+
+        // note: 省略した説明の件数を補足する
+        if descs.len() > max_descs {
+            msg += "\n\t";
+            msg += &InternalLog::DescriptionsOmitted { count: descs.len() - max_descs }.translate(&self.lang).msg;
+        }
+
+        return match &log.source_loc {
+            Some(v) if self.show_source_loc => format!("{}\n\tat {}", msg, v),
+            _ => msg,
+        };
+    }
+
+    fn format_label_prefix(&self) -> String {
+        // note: 複数のコンソールを併用する際の出力元ラベル
+        return match &self.label {
+            Some(v) => format!("[{}] ", v),
+            None => String::new(),
+        };
+    }
+
+    // note: 絶対パスを OSC 8 のハイパーリンクで囲む
+    fn hyperlink_paths_in(msg: &str) -> String {
+        let path_regex = PATH_REGEX.get_or_init(|| Regex::new(r"(^|\s)(/[^\s]+|[A-Za-z]:\\[^\s]*)").unwrap());
+
+        return path_regex.replace_all(msg, |caps: &Captures| {
+            let path = &caps[2];
+            let url_path = Console::encode_url_path(&path.replace('\\', "/"));
+
+            let url = if url_path.starts_with('/') {
+                format!("file://{}", url_path)
+            } else {
+                format!("file:///{}", url_path)
+            };
+
+            return format!("{}\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", &caps[1], url, path);
+        }).to_string();
+    }
+
+    // note: URL のパスとしてそのまま使えない文字をパーセントエンコードする
+    fn encode_url_path(path: &str) -> String {
+        let mut encoded_path = String::new();
+
+        for each_byte in path.bytes() {
+            match each_byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => encoded_path.push(each_byte as char),
+                _ => encoded_path.push_str(&format!("%{:02X}", each_byte)),
+            }
+        }
+
+        return encoded_path;
+    }
+
+    // note: "^^^ here" のような行のキャレット部分をログの種類の色で強調する
+    fn highlight_caret_lines(msg: &str, color: &str) -> String {
+        let lines: Vec<String> = msg.split('\n').map(|each_line| {
+            let trimmed_line = each_line.trim_start();
+
+            if !trimmed_line.starts_with(['^', '~']) {
+                return each_line.to_string();
+            }
+
+            let indent = &each_line[..each_line.len() - trimmed_line.len()];
+            let caret_len = trimmed_line.chars().take_while(|c| ['^', '~'].contains(c)).count();
+            let (carets, rest) = trimmed_line.split_at(caret_len);
+
+            return format!("{}{}{}\x1b[m{}", indent, color, carets, rest);
+        }).collect();
+
+        return lines.join("\n");
+    }
+
+    fn format_log_file_writing_failure_log(&self) -> String {
+        let err_log_kind = ConsoleLogKind::Error;
+        let color = if self.is_color_enabled() {
+            Some(self.get_color_escape(&err_log_kind))
+        } else {
+            None
+        };
+
+        return Console::format_title(color.as_deref(), &self.get_kind_label(&err_log_kind), "log file writing failure");
+    }
+
+    fn format_title(color: Option<&str>, kind: &str, title: &str) -> String {
+        let (color_begin, color_end) = match color {
+            Some(v) => (v.to_string(), "\x1b[m".to_string()),
+            None => (String::new(), String::new()),
+        };
+
+        return format!("{}[{}]{} {}", color_begin, kind, color_end, title);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::TempFilePath;
+
+    use chrono::TimeZone;
+
+    #[test]
+    fn absorb_moves_logs_of_other_console() {
+        let mut cons = Console::new("en".to_string(), ConsoleLogLimit::NoLimit);
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Error, "error".to_string()));
+
+        let mut other_cons = Console::new("en".to_string(), ConsoleLogLimit::NoLimit);
+        other_cons.append_log(ConsoleLog::new(ConsoleLogKind::Note, "note".to_string()));
+
+        cons.absorb(other_cons);
+
+        assert_eq!(cons.count_logs(&ConsoleLogKind::Error), 1);
+        assert_eq!(cons.count_logs(&ConsoleLogKind::Note), 1);
+        assert_eq!(cons.log_list[1].msg, "note");
+    }
+
+    #[test]
+    fn compact_omits_blank_lines_between_logs() {
+        let mut cons = Console::new("en".to_string(), ConsoleLogLimit::NoLimit);
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Error, "first".to_string()));
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Note, "second".to_string()));
+
+        assert_eq!(cons.render_to_string(), "[err] first\n\n[note] second\n\n");
+
+        cons.compact = true;
+        assert_eq!(cons.render_to_string(), "[err] first\n[note] second\n");
+    }
+
+    #[test]
+    fn color_mode_overrides_terminal_detection() {
+        let mut cons = Console::new("en".to_string(), ConsoleLogLimit::NoLimit);
+
+        cons.color_mode = ColorMode::Never;
+        assert!(!cons.is_color_enabled());
+
+        cons.color_mode = ColorMode::Always;
+        assert!(cons.is_color_enabled());
+    }
+
+    #[test]
+    fn log_kind_round_trips_through_display() {
+        let custom_kind = ConsoleLogKind::Custom { name: "hint".to_string(), color: 36, severity: 0 };
+
+        for each_kind in [ConsoleLogKind::Error, ConsoleLogKind::Warning, ConsoleLogKind::Note, custom_kind] {
+            assert_eq!(each_kind.to_string().parse::<ConsoleLogKind>(), Ok(each_kind));
+        }
+
+        assert_eq!("hint:36".parse::<ConsoleLogKind>(), Err(InternalLog::UnknownLogKind { value: "hint:36".to_string() }));
+    }
+
+    #[test]
+    fn verbosity_controls_note_visibility() {
+        let mut cons = Console::new("en".to_string(), ConsoleLogLimit::NoLimit);
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Note, "note".to_string()));
+
+        cons.set_verbosity(0);
+        assert_eq!(cons.render_to_string(), "");
+
+        cons.set_verbosity(1);
+        assert_eq!(cons.render_to_string(), "[note] note\n\n");
+    }
+
+    #[test]
+    fn log_limit_applies_to_terminal_and_file_output() {
+        let mut cons = Console::new("en".to_string(), ConsoleLogLimit::Limited(2));
+        cons.log_file_header = None;
+
+        for each_i in 1..=5 {
+            cons.append_log(ConsoleLog::new(ConsoleLogKind::Error, format!("error {}", each_i)));
+        }
+
+        assert_eq!(cons.render_to_string(), "[err] error 1\n\n[err] error 2\n\n[err] log limit 2 exceeded\n\n");
+
+        let path = TempFilePath::new("cons-util-test").unwrap();
+        cons.write_log_files(vec![LogFile::new(LogFileKind::ConsoleLogs, path.to_string())]).unwrap();
+        assert_eq!(path.read().unwrap(), "[err] error 1\n\n[err] error 2\n\n[err] log limit 2 exceeded\n");
+    }
+
+    #[test]
+    fn max_output_bytes_truncates_log_larger_than_budget() {
+        let mut cons = Console::new("en".to_string(), ConsoleLogLimit::NoLimit);
+        cons.max_output_bytes = Some(16);
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Error, "x".repeat(32)));
+
+        assert_eq!(cons.render_to_string(), "[note] output truncated due to output size limit\n\n");
+        assert_eq!(cons.render(&mut Vec::<u8>::new(), false), 0);
+    }
+
+    #[test]
+    fn rollback_discards_logs_after_checkpoint() {
+        let mut cons = Console::new("en".to_string(), ConsoleLogLimit::NoLimit);
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Warning, "kept".to_string()));
+
+        let checkpoint = cons.checkpoint();
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Error, "discarded".to_string()));
+        cons.append_separator();
+        cons.rollback_to(checkpoint);
+
+        assert!(!cons.has_errors());
+        assert_eq!(cons.render_to_string(), "[warn] kept\n\n");
+    }
+
+    #[test]
+    fn print_counts_uses_fixed_format() {
+        let mut cons = Console::new("ja".to_string(), ConsoleLogLimit::NoLimit);
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Error, "error".to_string()));
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Error, "error".to_string()));
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Note, "note".to_string()));
+        cons.append_separator();
+
+        let mut buf = Vec::<u8>::new();
+        cons.print_counts(&mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "errors=2 warnings=0 notes=1\n");
+    }
+
+    define_logs! {
+        enum ReplayTestLog {
+            FileNotFound { path: String } => {
+                kind: Error,
+                en: "file `{path}` not found",
+                ja: "ファイル `{path}` が見つかりません",
+            },
+        }
+    }
+
+    #[test]
+    fn replay_file_is_rendered_in_loading_language() {
+        let mut cons = Console::new("en".to_string(), ConsoleLogLimit::NoLimit);
+        cons.append_log(ReplayTestLog::FileNotFound { path: "a\tb.txt".to_string() }.translate("en"));
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Note, "plain\n\tdesc".to_string()));
+
+        let path = TempFilePath::new("cons-util-test").unwrap();
+        cons.write_log_files(vec![LogFile::new(LogFileKind::Replayable, path.to_string())]).unwrap();
+
+        let mut ja_cons = Console::new("ja".to_string(), ConsoleLogLimit::NoLimit);
+        ja_cons.register_translations("ja", ReplayTestLog::get_replay_translations("ja"));
+        let logs = ja_cons.load_replay(&path).unwrap();
+
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].kind, ConsoleLogKind::Error);
+        assert_eq!(logs[0].msg, "ファイル `a\tb.txt` が見つかりません");
+        assert_eq!(logs[0].get_replay(), ReplayTestLog::FileNotFound { path: "a\tb.txt".to_string() }.to_replay().as_ref());
+        assert_eq!(logs[1].msg, "plain\n\tdesc");
+
+        // note: 翻訳が登録されていない場合は読み込めない
+        assert!(cons.load_replay(&path).is_err());
+    }
+
+    #[test]
+    fn timestamps_use_clock_and_format() {
+        let mut cons = ConsoleBuilder::new().label("tool".to_string()).build();
+        cons.clock = Box::new(|| Local.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap());
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Warning, "warning".to_string()));
+
+        assert_eq!(cons.render_to_string(), "[tool] [warn] warning\n\n");
+
+        cons.show_timestamps = true;
+        assert_eq!(cons.render_to_string(), "03:04:05 [tool] [warn] warning\n\n");
+
+        cons.set_timestamp_format("%Y-%m-%d %H:%M".to_string()).unwrap();
+        assert_eq!(cons.render_to_string(), "2026-01-02 03:04 [tool] [warn] warning\n\n");
+    }
+
+    #[test]
+    fn custom_kinds_are_counted_by_severity() {
+        let mut cons = Console::new("en".to_string(), ConsoleLogLimit::NoLimit);
+        let security_kind = ConsoleLogKind::Custom { name: "security".to_string(), color: 35, severity: 3 };
+        cons.append_log(ConsoleLog::new(security_kind.clone(), "leak".to_string()));
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Custom { name: "hint".to_string(), color: 36, severity: 0 }, "hint".to_string()));
+
+        let mut buf = Vec::<u8>::new();
+        cons.print_counts(&mut buf).unwrap();
+        assert!(cons.has_errors());
+        assert_eq!(String::from_utf8(buf).unwrap(), "errors=1 warnings=0 notes=1\n");
+        assert_eq!(cons.count_logs(&security_kind), 1);
+
+        let mut buf = Vec::<u8>::new();
+        cons.output_ndjson(&mut buf).unwrap();
+        assert!(String::from_utf8(buf).unwrap().starts_with("{\"kind\":\"security\","));
+    }
+
+    #[test]
+    fn fail_fast_ignores_dropped_errors() {
+        let mut cons = Console::new("en".to_string(), ConsoleLogLimit::NoLimit);
+        cons.fail_fast = true;
+        cons.suppress(Box::new(|log| log.msg == "suppressed"));
+
+        assert_eq!(cons.try_append_log(ConsoleLog::new(ConsoleLogKind::Error, "suppressed".to_string())), Ok(()));
+
+        cons.ignore_logs = true;
+        assert_eq!(cons.try_append_log(ConsoleLog::new(ConsoleLogKind::Error, "ignored".to_string())), Ok(()));
+        assert!(!cons.has_errors());
+
+        cons.ignore_logs = false;
+        assert_eq!(cons.try_append_log(ConsoleLog::new(ConsoleLogKind::Error, "appended".to_string())), Err(()));
+    }
+
+    #[test]
+    fn multi_line_desc_counts_as_one_description() {
+        let lines: Vec<Box<dyn ConsoleLogTranslator>> = ["a", "b", "c"].iter()
+            .map(|each_line| Box::new(PlainText(ConsoleLogKind::Note, each_line.to_string())) as Box<dyn ConsoleLogTranslator>)
+            .collect();
+
+        let log = ConsoleLog::from_parts(ConsoleLogKind::Error, "title".to_string(), vec!["first".to_string()])
+            .with_desc(&MultiLineDesc(lines), "en");
+
+        assert_eq!(log.get_title(), "title");
+        assert_eq!(log.get_descs(), vec!["first", "a\nb\nc"]);
+
+        let mut cons = Console::new("en".to_string(), ConsoleLogLimit::NoLimit);
+        cons.max_descs_per_log = Some(2);
+        cons.append_log(log);
+        assert_eq!(cons.render_to_string(), "[err] title\n\tfirst\n\ta\nb\nc\n\n");
+
+        cons.max_descs_per_log = Some(1);
+        assert!(!cons.render_to_string().contains("a\nb"));
+    }
+
+    #[test]
+    fn ndjson_includes_source_loc() {
+        let mut cons = Console::new("en".to_string(), ConsoleLogLimit::NoLimit);
+        let source_loc = SourceLocation { file: "src/main.rs", line: 12, column: 5 };
+        cons.append_log(ConsoleLog::from_parts(ConsoleLogKind::Error, "title".to_string(), vec!["desc".to_string()]).with_source_loc(source_loc));
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Note, "note".to_string()));
+
+        let mut buf = Vec::<u8>::new();
+        cons.output_ndjson(&mut buf).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            concat!(
+                "{\"kind\":\"error\",\"code\":null,\"source_loc\":{\"file\":\"src/main.rs\",\"line\":12,\"column\":5},\"title\":\"title\",\"descs\":[\"desc\"]}\n",
+                "{\"kind\":\"note\",\"code\":null,\"source_loc\":null,\"title\":\"note\",\"descs\":[]}\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn github_actions_output_ignores_source_loc() {
+        let mut cons = Console::new("en".to_string(), ConsoleLogLimit::NoLimit);
+        let source_loc = SourceLocation { file: "src/main.rs", line: 12, column: 5 };
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Error, "title\n\tdesc".to_string()).with_source_loc(source_loc));
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Note, "100%".to_string()));
+
+        let mut buf = Vec::<u8>::new();
+        cons.output_github_actions(&mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "::error::title%0A\tdesc\n::notice::100%25\n");
+    }
+
+    #[test]
+    fn stream_to_file_matches_write_log_files() {
+        let mut cons = Console::new("en".to_string(), ConsoleLogLimit::NoLimit);
+        cons.log_file_timestamp = false;
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Error, "error".to_string()));
+        cons.append_separator();
+        cons.append_log(ConsoleLog::new(ConsoleLogKind::Note, "note".to_string()));
+
+        for (compact, ensure_trailing_newline) in [(false, false), (true, false), (true, true)] {
+            cons.compact = compact;
+            cons.ensure_trailing_newline = ensure_trailing_newline;
+
+            let written_path = TempFilePath::new("cons-util-test").unwrap();
+            let streamed_path = TempFilePath::new("cons-util-test").unwrap();
+            cons.write_log_files(vec![LogFile::new(LogFileKind::ConsoleLogs, written_path.to_string())]).unwrap();
+            cons.stream_to_file(&streamed_path).unwrap();
+
+            assert_eq!(streamed_path.read().unwrap(), written_path.read().unwrap());
+            assert_eq!(streamed_path.read().unwrap().ends_with('\n'), !compact || ensure_trailing_newline);
+        }
+    }
+
+    #[test]
+    fn stream_to_file_skips_empty_file() {
+        let mut cons = Console::new("en".to_string(), ConsoleLogLimit::NoLimit);
+        cons.write_empty_files = false;
+
+        let path = TempFilePath::new("cons-util-test").unwrap();
+        std::fs::remove_file(path.to_string()).unwrap();
+        cons.stream_to_file(&path).unwrap();
+
+        assert!(!path.exists());
     }
 }
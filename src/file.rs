@@ -4,23 +4,59 @@ use {
     crate::cons::*,
 
     std::{
-        env::current_dir,
+        collections::HashSet,
+        env::{
+            current_dir,
+            temp_dir,
+        },
         fmt::{
             Display,
             Formatter,
         },
         fs::*,
         io::*,
-        path::PathBuf,
-        time::SystemTime,
+        ops::Deref,
+        path::{
+            Component,
+            PathBuf,
+        },
+        process,
+        sync::atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+        thread,
+        time::{
+            Duration,
+            SystemTime,
+            UNIX_EPOCH,
+        },
         result::Result,
     },
 };
 
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+const READ_BUFFER_SIZE: usize = 8192;
+
 pub type FileManResult<T> = Result<T, FileManLog>;
 
 #[derive(Clone, cons_util_derive::ConsoleLogTranslator, Debug, PartialEq)]
 pub enum FileManLog {
+    #[translate(
+        kind = "E",
+        en = "destination already exists\n\tpath: {path}",
+        ja = "移動先が既に存在します\n\tパス: {path}",
+    )]
+    DestinationExists { path: String },
+
+    #[translate(
+        kind = "E",
+        en = "expected directory path not file path\n\tpath: {path}",
+        ja = "ファイルパスでなくディレクトリパスが必要です\n\tパス: {path}",
+    )]
+    ExpectedDirectoryPathNotFilePath { path: String },
+
     #[translate(
         kind = "E",
         en = "expected file path not directory path",
@@ -28,6 +64,13 @@ pub enum FileManLog {
     )]
     ExpectedFilePathNotDirectoryPath,
 
+    #[translate(
+        kind = "E",
+        en = "failed to copy file\n\tpath: {path}",
+        ja = "ファイルのコピーに失敗しました\n\tパス: {path}",
+    )]
+    FailedToCopyFile { path: String },
+
     #[translate(
         kind = "E",
         en = "failed to get current directory",
@@ -35,6 +78,20 @@ pub enum FileManLog {
     )]
     FailedToGetCurrentDirectory,
 
+    #[translate(
+        kind = "E",
+        en = "failed to lock file\n\tpath: {path}",
+        ja = "ファイルのロックに失敗しました\n\tパス: {path}",
+    )]
+    FailedToLockFile { path: String },
+
+    #[translate(
+        kind = "E",
+        en = "failed to move file to trash\n\tpath: {path}",
+        ja = "ファイルをごみ箱へ移動できませんでした\n\tパス: {path}",
+    )]
+    FailedToMoveToTrash { path: String },
+
     #[translate(
         kind = "E",
         en = "failed to open file\n\tpath: {path}",
@@ -49,6 +106,27 @@ pub enum FileManLog {
     )]
     FailedToOpenFileOrDirectory { path: String },
 
+    #[translate(
+        kind = "E",
+        en = "failed to parse file\n\tpath: {path}",
+        ja = "ファイルの解析に失敗しました\n\tパス: {path}",
+    )]
+    FailedToParseFile { path: String },
+
+    #[translate(
+        kind = "E",
+        en = "failed to parse file\n\tpath: {path}\n\tposition: {line}:{column}",
+        ja = "ファイルの解析に失敗しました\n\tパス: {path}\n\t位置: {line}:{column}",
+    )]
+    FailedToParseFileAt { path: String, line: usize, column: usize },
+
+    #[translate(
+        kind = "E",
+        en = "failed to read directory\n\tpath: {path}",
+        ja = "ディレクトリの読み込みに失敗しました\n\tパス: {path}",
+    )]
+    FailedToReadDirectory { path: String },
+
     #[translate(
         kind = "E",
         en = "failed to read file\n\tpath: {path}",
@@ -56,6 +134,20 @@ pub enum FileManLog {
     )]
     FailedToReadFile { path: String },
 
+    #[translate(
+        kind = "E",
+        en = "failed to rename file\n\tpath: {path}",
+        ja = "ファイル名の変更に失敗しました\n\tパス: {path}",
+    )]
+    FailedToRenameFile { path: String },
+
+    #[translate(
+        kind = "E",
+        en = "failed to serialize value\n\tpath: {path}",
+        ja = "値のシリアライズに失敗しました\n\tパス: {path}",
+    )]
+    FailedToSerialize { path: String },
+
     #[translate(
         kind = "E",
         en = "failed to write file\n\tpath: {path}",
@@ -76,6 +168,168 @@ pub enum FileManLog {
         ja = "パスが存在しません\n\tパス: {path}",
     )]
     PathDoesNotExist { path: String },
+
+    #[translate(
+        kind = "E",
+        en = "environment variable `{name}` is not defined",
+        ja = "環境変数 `{name}` が定義されていません",
+    )]
+    UndefinedEnvVar { name: String },
+
+    #[translate(
+        kind = "E",
+        en = "unsupported byte order mark\n\tpath: {path}\n\tencoding: {encoding}\n\tonly UTF-8 is supported",
+        ja = "サポートされていないバイトオーダーマークです\n\tパス: {path}\n\tエンコーディング: {encoding}\n\tUTF-8 のみサポートしています",
+    )]
+    UnsupportedByteOrderMark { path: String, encoding: String },
+}
+
+pub const STDIN_MARKER: &str = "-";
+
+const UTF8_BOM: &str = "\u{feff}";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &str {
+        return match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        };
+    }
+}
+
+#[cfg(feature = "diff")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiffLine {
+    Added(String),
+    Removed(String),
+    Unchanged(String),
+}
+
+// note: 各パスを正規化した上で共通する最も深いディレクトリを返す
+// 共通部分がない (ルートが異なる) 場合や paths が空の場合は None を返す
+pub fn common_ancestor(paths: &[FilePath]) -> FileManResult<Option<FilePath>> {
+    let mut ancestor_path_obj: Option<PathBuf> = None;
+
+    for each_path in paths {
+        let canonical_path = each_path.canonicalize()?;
+
+        // note: ファイルの場合はその親ディレクトリを比較対象にする
+        let dir_path_obj = if canonical_path.is_dir() {
+            canonical_path.1.clone()
+        } else {
+            match canonical_path.1.parent() {
+                Some(v) => v.to_path_buf(),
+                None => return Ok(None),
+            }
+        };
+
+        ancestor_path_obj = match ancestor_path_obj {
+            Some(v) => {
+                let common_path_obj: PathBuf = v.components()
+                    .zip(dir_path_obj.components())
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a)
+                    .collect();
+
+                if common_path_obj.as_os_str().is_empty() {
+                    return Ok(None);
+                }
+
+                Some(common_path_obj)
+            },
+            None => Some(dir_path_obj),
+        };
+    }
+
+    return Ok(ancestor_path_obj.map(FilePath::from));
+}
+
+// note: .tar.gz の各エントリの名前と内容を返す (ディレクトリなどのエントリは含めない)
+#[cfg(feature = "archive")]
+pub fn read_tar_gz(archive: &FilePath) -> FileManResult<Vec<(String, Vec<u8>)>> {
+    archive.ensure_exists()?;
+    archive.ensure_be_file()?;
+
+    let decoder = flate2::read::GzDecoder::new(BufReader::new(archive.open()?));
+    let mut tar_archive = tar::Archive::new(decoder);
+
+    let entries = match tar_archive.entries() {
+        Ok(v) => v,
+        Err(_) => return Err(FileManLog::FailedToReadFile { path: archive.to_string() }),
+    };
+
+    let mut files = Vec::<(String, Vec<u8>)>::new();
+
+    for each_entry in entries {
+        let mut entry = match each_entry {
+            Ok(v) => v,
+            Err(_) => return Err(FileManLog::FailedToReadFile { path: archive.to_string() }),
+        };
+
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let name = match entry.path() {
+            Ok(v) => v.to_string_lossy().to_string(),
+            Err(_) => return Err(FileManLog::FailedToReadFile { path: archive.to_string() }),
+        };
+
+        let mut content = Vec::<u8>::new();
+
+        if entry.read_to_end(&mut content).is_err() {
+            return Err(FileManLog::FailedToReadFile { path: archive.to_string() });
+        }
+
+        files.push((name, content));
+    }
+
+    return Ok(files);
+}
+
+// note: 2 つのテキストファイルを行単位で比較する
+#[cfg(feature = "diff")]
+pub fn text_diff(a: &FilePath, b: &FilePath) -> FileManResult<Vec<DiffLine>> {
+    let a_content = a.read()?;
+    let b_content = b.read()?;
+    let diff = similar::TextDiff::from_lines(a_content.as_str(), b_content.as_str());
+
+    let lines = diff.iter_all_changes().map(|each_change| {
+        let line = each_change.value().trim_end_matches(['\n', '\r']).to_string();
+
+        return match each_change.tag() {
+            similar::ChangeTag::Insert => DiffLine::Added(line),
+            similar::ChangeTag::Delete => DiffLine::Removed(line),
+            similar::ChangeTag::Equal => DiffLine::Unchanged(line),
+        };
+    }).collect();
+
+    return Ok(lines);
+}
+
+pub fn read_stdin() -> FileManResult<String> {
+    let mut content = String::new();
+
+    return match stdin().read_to_string(&mut content) {
+        Ok(_) => Ok(content),
+        Err(_) => Err(FileManLog::FailedToReadFile { path: "<stdin>".to_string() }),
+    };
+}
+
+// note: stat() 実行時点の属性のスナップショットであり, 以降の変更は反映されない
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileStat {
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    pub created: Option<SystemTime>,
+    pub is_dir: bool,
+    pub is_symlink: bool,
 }
 
 #[derive(Clone, Eq, Hash, PartialEq)]
@@ -90,6 +344,77 @@ impl FilePath {
         return FilePath(path.clone().into_os_string().into_string().unwrap(), path);
     }
 
+    // note: $VAR, ${VAR}, %VAR% の形式の環境変数を展開する
+    // 変数名として解釈できない $ や % はそのまま残す
+    pub fn expand_env(&self) -> FileManResult<FilePath> {
+        let chars: Vec<char> = self.0.chars().collect();
+        let mut expanded_path = String::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let (name, next_i) = match chars[i] {
+                '$' if chars.get(i + 1) == Some(&'{') => {
+                    match chars[i + 2..].iter().position(|c| *c == '}') {
+                        Some(v) => (chars[i + 2..i + 2 + v].iter().collect::<String>(), i + 3 + v),
+                        None => (String::new(), i + 1),
+                    }
+                },
+                '$' => {
+                    let len = chars[i + 1..].iter().take_while(|c| c.is_ascii_alphanumeric() || **c == '_').count();
+                    (chars[i + 1..i + 1 + len].iter().collect::<String>(), i + 1 + len)
+                },
+                '%' => {
+                    match chars[i + 1..].iter().position(|c| *c == '%') {
+                        Some(v) if chars[i + 1..i + 1 + v].iter().all(|c| c.is_ascii_alphanumeric() || *c == '_') => {
+                            (chars[i + 1..i + 1 + v].iter().collect::<String>(), i + 2 + v)
+                        },
+                        _ => (String::new(), i + 1),
+                    }
+                },
+                c => {
+                    expanded_path.push(c);
+                    i += 1;
+                    continue;
+                },
+            };
+
+            if name.is_empty() {
+                expanded_path.push(chars[i]);
+                i += 1;
+                continue;
+            }
+
+            match std::env::var(&name) {
+                Ok(v) => expanded_path.push_str(&v),
+                Err(_) => return Err(FileManLog::UndefinedEnvVar { name: name }),
+            }
+
+            i = next_i;
+        }
+
+        return Ok(FilePath::new(expanded_path));
+    }
+
+    // note: 一時ディレクトリ下に衝突しない名前で空ファイルを作成する
+    pub fn temp_file(prefix: &str) -> FileManResult<FilePath> {
+        loop {
+            let count = TEMP_FILE_COUNTER.fetch_add(1, Ordering::SeqCst);
+
+            let nanos = match SystemTime::now().duration_since(UNIX_EPOCH) {
+                Ok(v) => v.subsec_nanos(),
+                Err(_) => 0,
+            };
+
+            let path = FilePath::from(temp_dir().join(format!("{}-{}-{}-{}", prefix, process::id(), count, nanos)));
+
+            match OpenOptions::new().write(true).create_new(true).open(&path.1) {
+                Ok(_) => return Ok(path),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(_) => return Err(FileManLog::FailedToOpenFile { path: path.0 }),
+            }
+        }
+    }
+
     pub fn to_absolute(&self) -> FileManResult<FilePath> {
         let curr_dir_path_obj = match current_dir() {
             Ok(v) => v,
@@ -99,6 +424,10 @@ impl FilePath {
         return Ok(FilePath::from(curr_dir_path_obj.join(&self.0)));
     }
 
+    pub fn is_stdin_marker(&self) -> bool {
+        return self.0 == STDIN_MARKER;
+    }
+
     pub fn exists(&self) -> bool {
         return self.1.exists();
     }
@@ -111,20 +440,218 @@ impl FilePath {
         return !self.1.is_dir();
     }
 
+    #[cfg(unix)]
+    pub fn is_hidden(&self) -> FileManResult<bool> {
+        self.ensure_exists()?;
+
+        let is_hidden = match self.1.file_name() {
+            Some(v) => v.to_string_lossy().starts_with('.'),
+            None => false,
+        };
+
+        return Ok(is_hidden);
+    }
+
+    #[cfg(windows)]
+    pub fn is_hidden(&self) -> FileManResult<bool> {
+        use std::os::windows::fs::MetadataExt;
+
+        // note: FILE_ATTRIBUTE_HIDDEN
+        return Ok(self.metadata()?.file_attributes() & 0x2 != 0);
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    pub fn is_hidden(&self) -> FileManResult<bool> {
+        return Err(FileManLog::MetadataIsNotAvailableOnThisPlatform);
+    }
+
+    // note: Windows のボリューム番号は安定版の std から取得できないため未対応とする
+    #[cfg(unix)]
+    pub fn same_device(&self, other: &FilePath) -> FileManResult<bool> {
+        use std::os::unix::fs::MetadataExt;
+
+        return Ok(self.metadata()?.dev() == other.metadata()?.dev());
+    }
+
+    #[cfg(not(unix))]
+    pub fn same_device(&self, _other: &FilePath) -> FileManResult<bool> {
+        return Err(FileManLog::MetadataIsNotAvailableOnThisPlatform);
+    }
+
+    // note: 実際に読み込み用に開けるかどうかで判定する
+    #[cfg(any(unix, windows))]
+    pub fn is_readable(&self) -> FileManResult<bool> {
+        self.ensure_exists()?;
+
+        let is_readable = if self.is_dir() {
+            read_dir(&self.1).is_ok()
+        } else {
+            File::open(&self.1).is_ok()
+        };
+
+        return Ok(is_readable);
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    pub fn is_readable(&self) -> FileManResult<bool> {
+        return Err(FileManLog::MetadataIsNotAvailableOnThisPlatform);
+    }
+
+    // note: ファイルは書き込み用に開けるかどうかで判定する (内容は変更しない)
+    // ディレクトリは開いて確かめられないため読み取り専用属性で判定する
+    #[cfg(any(unix, windows))]
+    pub fn is_writable(&self) -> FileManResult<bool> {
+        self.ensure_exists()?;
+
+        if self.is_dir() {
+            return Ok(!self.metadata()?.permissions().readonly());
+        }
+
+        return Ok(OpenOptions::new().write(true).open(&self.1).is_ok());
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    pub fn is_writable(&self) -> FileManResult<bool> {
+        return Err(FileManLog::MetadataIsNotAvailableOnThisPlatform);
+    }
+
     pub fn is_same_as(&self, path: &FilePath) -> FileManResult<bool> {
-        return match same_file::is_same_file(&self.0, &path.0) {
+        // note: 相対パスと絶対パスを同様に比較するため両方を絶対パスに揃える
+        let self_abs_path = self.to_absolute()?;
+        let target_abs_path = path.to_absolute()?;
+
+        if self_abs_path.canonical_string()? == target_abs_path.canonical_string()? {
+            return Ok(true);
+        }
+
+        return match same_file::is_same_file(&self_abs_path.1, &target_abs_path.1) {
             Ok(v) => Ok(v),
             Err(_) => Err(FileManLog::FailedToOpenFileOrDirectory { path: format!("{}; {}", self.0, path.0) }),
         };
     }
 
+    // note: パスが存在しない場合はエラーになる
+    // Windows では表示用に \\?\ プレフィクスを取り除く
+    pub fn canonicalize(&self) -> FileManResult<FilePath> {
+        let canonical_path_obj = match self.1.canonicalize() {
+            Ok(v) => v,
+            Err(_) => return Err(FileManLog::FailedToOpenFileOrDirectory { path: self.0.clone() }),
+        };
+
+        return Ok(FilePath::from(FilePath::strip_verbatim_prefix(canonical_path_obj)));
+    }
+
+    #[cfg(windows)]
+    fn strip_verbatim_prefix(path: PathBuf) -> PathBuf {
+        let path_str = path.to_string_lossy().to_string();
+
+        return match path_str.strip_prefix(r"\\?\UNC\") {
+            Some(v) => PathBuf::from(format!(r"\\{}", v)),
+            None => match path_str.strip_prefix(r"\\?\") {
+                Some(v) => PathBuf::from(v),
+                None => path,
+            },
+        };
+    }
+
+    #[cfg(not(windows))]
+    fn strip_verbatim_prefix(path: PathBuf) -> PathBuf {
+        return path;
+    }
+
+    // note: 正規化し区切り文字を "/" に揃えた文字列を返す (HashMap のキーなどに使う)
+    pub fn canonical_string(&self) -> FileManResult<String> {
+        return Ok(self.canonicalize()?.display_posix());
+    }
+
     pub fn join(&self, rel_path: &FilePath) -> FileManResult<FilePath> {
-        let joined_path_obj = self.1.join(&rel_path.0);
+        return FilePath::from(self.1.join(&rel_path.0)).canonicalize();
+    }
+
+    pub fn file_size(&self) -> FileManResult<u64> {
+        return Ok(self.metadata()?.len());
+    }
+
+    pub fn dir_size(&self) -> FileManResult<u64> {
+        let mut size = 0u64;
+
+        for each_path in self.walk()? {
+            size += each_path.file_size()?;
+        }
+
+        return Ok(size);
+    }
+
+    // note: ディレクトリ下のファイルを再帰的に列挙する
+    pub fn walk(&self) -> FileManResult<Vec<FilePath>> {
+        return self.walk_until(None);
+    }
 
-        return match joined_path_obj.canonicalize() {
-            Ok(v) => Ok(FilePath::from(v)),
-            Err(_) => Err(FileManLog::FailedToOpenFileOrDirectory { path: joined_path_obj.to_str().unwrap().to_string() }),
+    pub fn walk_filtered(&self, filter: impl Fn(&FilePath) -> bool) -> FileManResult<Vec<FilePath>> {
+        return Ok(self.walk()?.into_iter().filter(|each_path| filter(each_path)).collect());
+    }
+
+    // note: 1 回目の走査でファイルの総数を求め, ファイルごとに on_each(処理済み数, 総数, パス) を呼ぶ
+    pub fn walk_with_progress(&self, mut on_each: impl FnMut(usize, usize, &FilePath)) -> FileManResult<Vec<FilePath>> {
+        let paths = self.walk()?;
+        let total = paths.len();
+
+        for (i, each_path) in paths.iter().enumerate() {
+            on_each(i + 1, total, each_path);
+        }
+
+        return Ok(paths);
+    }
+
+    // note: max_depth はルートからの深さで, 0 の場合はルート直下のみを対象とする
+    pub fn walk_with_depth(&self, max_depth: usize) -> FileManResult<Vec<FilePath>> {
+        return self.walk_until(Some(max_depth));
+    }
+
+    fn walk_until(&self, max_depth: Option<usize>) -> FileManResult<Vec<FilePath>> {
+        self.ensure_exists()?;
+        self.ensure_be_dir()?;
+
+        let mut visited_dirs = HashSet::<PathBuf>::new();
+        let mut paths = Vec::<FilePath>::new();
+        self.walk_into(0, max_depth, &mut visited_dirs, &mut paths)?;
+        return Ok(paths);
+    }
+
+    fn walk_into(&self, depth: usize, max_depth: Option<usize>, visited_dirs: &mut HashSet<PathBuf>, paths: &mut Vec<FilePath>) -> FileManResult<()> {
+        // note: シンボリックリンクによる循環を避けるため訪問済みのディレクトリは辿らない
+        let canonical_path_obj = match self.1.canonicalize() {
+            Ok(v) => v,
+            Err(_) => return Err(FileManLog::FailedToOpenFileOrDirectory { path: self.0.clone() }),
+        };
+
+        if !visited_dirs.insert(canonical_path_obj) {
+            return Ok(());
+        }
+
+        let entries = match read_dir(&self.1) {
+            Ok(v) => v,
+            Err(_) => return Err(FileManLog::FailedToReadDirectory { path: self.0.clone() }),
         };
+
+        for each_entry in entries {
+            let entry_path = match each_entry {
+                Ok(v) => FilePath::from(v.path()),
+                Err(_) => return Err(FileManLog::FailedToReadDirectory { path: self.0.clone() }),
+            };
+
+            // note: リンク先が存在しないシンボリックリンクはファイルとして扱えないため飛ばす
+            if entry_path.is_dir() {
+                match max_depth {
+                    Some(v) if depth >= v => (),
+                    _ => entry_path.walk_into(depth + 1, max_depth, visited_dirs, paths)?,
+                }
+            } else if entry_path.1.exists() {
+                paths.push(entry_path);
+            }
+        }
+
+        return Ok(());
     }
 
     pub fn last_modified(&self) -> FileManResult<SystemTime> {
@@ -145,6 +672,66 @@ impl FilePath {
         };
     }
 
+    pub fn stat(&self) -> FileManResult<FileStat> {
+        let metadata = match symlink_metadata(&self.1) {
+            Ok(v) => v,
+            Err(_) => return Err(FileManLog::FailedToOpenFileOrDirectory { path: self.0.clone() }),
+        };
+
+        return Ok(FileStat {
+            size: metadata.len(),
+            modified: metadata.modified().ok(),
+            created: metadata.created().ok(),
+            is_dir: metadata.is_dir(),
+            is_symlink: metadata.file_type().is_symlink(),
+        });
+    }
+
+    // note: パスの存在を確認せず字句的に親ディレクトリを求める
+    // 存在確認が必要な場合は parent_dir() を使う
+    pub fn parent(&self) -> Option<FilePath> {
+        return match self.1.parent() {
+            Some(v) if v.as_os_str().is_empty() => Some(FilePath::new(".".to_string())),
+            Some(v) => Some(FilePath::from(v.to_path_buf())),
+            None => None,
+        };
+    }
+
+    // note: self の親ディレクトリを基準に rel を解決し, "." や ".." を字句的に取り除く
+    // join と異なり解決先が存在しなくてもよい
+    pub fn resolve_sibling(&self, rel: &str) -> FileManResult<FilePath> {
+        let parent = match self.parent() {
+            Some(v) => v,
+            None => return Err(FileManLog::ExpectedFilePathNotDirectoryPath),
+        };
+
+        let joined_path_obj = parent.1.join(rel);
+        let mut normalized_path_obj = PathBuf::new();
+
+        for each_component in joined_path_obj.components() {
+            match each_component {
+                Component::CurDir => (),
+                Component::ParentDir => {
+                    match normalized_path_obj.components().next_back() {
+                        Some(Component::Normal(_)) => {
+                            normalized_path_obj.pop();
+                        },
+                        // note: ルートより上へは辿らない
+                        Some(Component::RootDir) | Some(Component::Prefix(_)) => (),
+                        _ => normalized_path_obj.push(".."),
+                    }
+                },
+                _ => normalized_path_obj.push(each_component),
+            }
+        }
+
+        if normalized_path_obj.as_os_str().is_empty() {
+            normalized_path_obj.push(".");
+        }
+
+        return Ok(FilePath::from(normalized_path_obj));
+    }
+
     pub fn parent_dir(&self) -> FileManResult<Option<FilePath>> {
         if !self.exists() {
             return Err(FileManLog::PathDoesNotExist { path: self.0.clone() });
@@ -159,28 +746,133 @@ impl FilePath {
     }
 
     pub fn read(&self) -> FileManResult<String> {
-        self.ensure_exists()?;
-        self.ensure_be_file()?;
-
-        let content = match std::fs::read_to_string(&self.0) {
-            Ok(v) => v,
-            Err(_) => return Err(FileManLog::FailedToReadFile { path: self.0.clone() }),
-        };
+        return self.read_with_bom_handling(false);
+    }
 
-        return Ok(content);
+    pub fn read_bom_stripped(&self) -> FileManResult<String> {
+        return self.read_with_bom_handling(true);
     }
 
-    pub fn read_bytes(&self) -> FileManResult<Vec<u8>> {
-        self.ensure_exists()?;
-        self.ensure_be_file()?;
+    // note: strip_bom が真の場合は先頭の UTF-8 BOM を取り除く
+    // UTF-16 の BOM で始まる場合は UTF-8 として読めないためエラーにする
+    pub fn read_with_bom_handling(&self, strip_bom: bool) -> FileManResult<String> {
+        // note: "-" は標準入力として扱う
+        let content = if self.is_stdin_marker() {
+            read_stdin()?
+        } else {
+            self.ensure_exists()?;
+            self.ensure_be_file()?;
 
-        let mut reader = match File::open(&self.0) {
-            Ok(v) => BufReader::new(v),
-            Err(_) => return Err(FileManLog::FailedToOpenFile { path: self.0.clone() }),
-        };
+            let bytes = match std::fs::read(&self.0) {
+                Ok(v) => v,
+                Err(_) => return Err(FileManLog::FailedToReadFile { path: self.0.clone() }),
+            };
 
-        let mut bytes = Vec::<u8>::new();
-        let mut buf = [0; 4];
+            let encoding = match bytes.get(..2) {
+                Some([0xFF, 0xFE]) => Some("UTF-16LE"),
+                Some([0xFE, 0xFF]) => Some("UTF-16BE"),
+                _ => None,
+            };
+
+            if let Some(encoding) = encoding {
+                return Err(FileManLog::UnsupportedByteOrderMark { path: self.0.clone(), encoding: encoding.to_string() });
+            }
+
+            match String::from_utf8(bytes) {
+                Ok(v) => v,
+                Err(_) => return Err(FileManLog::FailedToReadFile { path: self.0.clone() }),
+            }
+        };
+
+        if strip_bom {
+            if let Some(v) = content.strip_prefix(UTF8_BOM) {
+                return Ok(v.to_string());
+            }
+        }
+
+        return Ok(content);
+    }
+
+    // note: UTF-8 として不正なバイト列は U+FFFD に置き換える
+    pub fn read_utf8_lossy(&self) -> FileManResult<String> {
+        self.ensure_exists()?;
+        self.ensure_be_file()?;
+
+        return match std::fs::read(&self.0) {
+            Ok(v) => Ok(String::from_utf8_lossy(&v).into_owned()),
+            Err(_) => Err(FileManLog::FailedToReadFile { path: self.0.clone() }),
+        };
+    }
+
+    // note: 呼び出し側のバッファを再利用して読み込む
+    pub fn read_into(&self, buf: &mut String) -> FileManResult<()> {
+        self.ensure_exists()?;
+        self.ensure_be_file()?;
+        buf.clear();
+
+        return match self.open()?.read_to_string(buf) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(FileManLog::FailedToReadFile { path: self.0.clone() }),
+        };
+    }
+
+    // note: 最初の start と, その後に現れる最初の end に挟まれた内容を返す
+    pub fn read_between_markers(&self, start: &str, end: &str) -> FileManResult<Option<String>> {
+        let content = self.read()?;
+
+        return match FilePath::find_between_markers(&content, start, end) {
+            Some((begin_i, end_i)) => Ok(Some(content[begin_i..end_i].to_string())),
+            None => Ok(None),
+        };
+    }
+
+    // note: マーカー自体と範囲外の内容は保ったまま書き換え, マーカーが見つかったかどうかを返す
+    pub fn replace_between_markers(&self, start: &str, end: &str, new_content: &str) -> FileManResult<bool> {
+        let content = self.read()?;
+
+        let (begin_i, end_i) = match FilePath::find_between_markers(&content, start, end) {
+            Some(v) => v,
+            None => return Ok(false),
+        };
+
+        let replaced_content = format!("{}{}{}", &content[..begin_i], new_content, &content[end_i..]);
+        self.write(&replaced_content)?;
+        return Ok(true);
+    }
+
+    fn find_between_markers(content: &str, start: &str, end: &str) -> Option<(usize, usize)> {
+        let begin_i = content.find(start)? + start.len();
+        let end_i = begin_i + content[begin_i..].find(end)?;
+        return Some((begin_i, end_i));
+    }
+
+    // note: 改行コードを "\n" に統一して読み込む
+    pub fn read_normalized(&self) -> FileManResult<String> {
+        return Ok(self.read()?.replace("\r\n", "\n"));
+    }
+
+    pub fn read_bytes_into(&self, buf: &mut Vec<u8>) -> FileManResult<()> {
+        self.ensure_exists()?;
+        self.ensure_be_file()?;
+        buf.clear();
+
+        return match self.open()?.read_to_end(buf) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(FileManLog::FailedToReadFile { path: self.0.clone() }),
+        };
+    }
+
+    pub fn read_bytes(&self) -> FileManResult<Vec<u8>> {
+        self.ensure_exists()?;
+        self.ensure_be_file()?;
+
+        let mut reader = match File::open(&self.0) {
+            Ok(v) => BufReader::new(v),
+            Err(_) => return Err(FileManLog::FailedToOpenFile { path: self.0.clone() }),
+        };
+
+        let mut bytes = Vec::<u8>::new();
+        let mut buf = [0; READ_BUFFER_SIZE];
 
         loop {
             match reader.read(&mut buf) {
@@ -200,6 +892,95 @@ impl FilePath {
         return Ok(bytes);
     }
 
+    // note: コールバックには読み込み済みバイト数と総バイト数 (取得できた場合) を渡す
+    pub fn read_bytes_with_progress(&self, mut on_progress: impl FnMut(u64, Option<u64>)) -> FileManResult<Vec<u8>> {
+        self.ensure_exists()?;
+        self.ensure_be_file()?;
+
+        let total = self.file_size().ok();
+
+        let mut reader = match File::open(&self.0) {
+            Ok(v) => BufReader::new(v),
+            Err(_) => return Err(FileManLog::FailedToOpenFile { path: self.0.clone() }),
+        };
+
+        let mut bytes = Vec::<u8>::new();
+        let mut buf = [0; READ_BUFFER_SIZE];
+
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => {
+                    bytes.extend_from_slice(&buf[..n]);
+                    on_progress(bytes.len() as u64, total);
+                },
+                Err(_) => return Err(FileManLog::FailedToReadFile { path: self.0.clone() }),
+            }
+        }
+
+        return Ok(bytes);
+    }
+
+    // note: chunk_size バイトずつ読み込む (最後のチャンクは短い場合がある)
+    pub fn read_chunks(&self, chunk_size: usize) -> FileManResult<impl Iterator<Item = FileManResult<Vec<u8>>>> {
+        self.ensure_exists()?;
+        self.ensure_be_file()?;
+
+        let mut reader = BufReader::new(self.open()?);
+        let path = self.0.clone();
+        let mut finished = false;
+
+        let chunks = std::iter::from_fn(move || {
+            if finished {
+                return None;
+            }
+
+            let mut chunk = Vec::<u8>::with_capacity(chunk_size);
+
+            return match (&mut reader).take(chunk_size as u64).read_to_end(&mut chunk) {
+                Ok(0) => None,
+                Ok(_) => Some(Ok(chunk)),
+                Err(_) => {
+                    finished = true;
+                    Some(Err(FileManLog::FailedToReadFile { path: path.clone() }))
+                },
+            };
+        });
+
+        return Ok(chunks);
+    }
+
+    // note: 末尾に区切り文字がない最後の区間も返す
+    pub fn read_until_byte(&self, delim: u8) -> FileManResult<Vec<Vec<u8>>> {
+        self.ensure_exists()?;
+        self.ensure_be_file()?;
+
+        let mut reader = match File::open(&self.0) {
+            Ok(v) => BufReader::new(v),
+            Err(_) => return Err(FileManLog::FailedToOpenFile { path: self.0.clone() }),
+        };
+
+        let mut segments = Vec::<Vec<u8>>::new();
+
+        loop {
+            let mut segment = Vec::<u8>::new();
+
+            match reader.read_until(delim, &mut segment) {
+                Ok(0) => break,
+                Ok(_) => {
+                    if segment.last() == Some(&delim) {
+                        segment.pop();
+                    }
+
+                    segments.push(segment);
+                },
+                Err(_) => return Err(FileManLog::FailedToReadFile { path: self.0.clone() }),
+            }
+        }
+
+        return Ok(segments);
+    }
+
     pub fn read_lines(&self) -> FileManResult<Vec<String>> {
         self.ensure_exists()?;
         self.ensure_be_file()?;
@@ -223,6 +1004,114 @@ impl FilePath {
         return Ok(lines);
     }
 
+    // note: ファイル末尾からブロック単位で遡って読み込み, 最後の n 行を元の順序で返す
+    // 行数が n に満たない場合はすべての行を返す
+    pub fn read_last_lines(&self, n: usize) -> FileManResult<Vec<String>> {
+        self.ensure_exists()?;
+        self.ensure_be_file()?;
+
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut file = self.open()?;
+        let mut pos = self.file_size()?;
+        let mut tail = Vec::<u8>::new();
+
+        while pos > 0 {
+            let read_size = std::cmp::min(READ_BUFFER_SIZE as u64, pos);
+            pos -= read_size;
+
+            let mut block = vec![0; read_size as usize];
+
+            if file.seek(SeekFrom::Start(pos)).is_err() || file.read_exact(&mut block).is_err() {
+                return Err(FileManLog::FailedToReadFile { path: self.0.clone() });
+            }
+
+            block.extend(tail);
+            tail = block;
+
+            // note: 末尾の改行は行の区切りとして数えない
+            let content_len = if tail.last() == Some(&b'\n') {
+                tail.len() - 1
+            } else {
+                tail.len()
+            };
+
+            if tail[..content_len].iter().filter(|b| **b == b'\n').count() >= n {
+                break;
+            }
+        }
+
+        let text = match String::from_utf8(tail) {
+            Ok(v) => v,
+            // note: ブロックの先頭で文字が分断された場合は最初の改行以降のみを使う
+            Err(e) => {
+                let bytes = e.into_bytes();
+
+                let first_line_end = match bytes.iter().position(|b| *b == b'\n') {
+                    Some(v) => v + 1,
+                    None => return Err(FileManLog::FailedToReadFile { path: self.0.clone() }),
+                };
+
+                match String::from_utf8(bytes[first_line_end..].to_vec()) {
+                    Ok(v) => v,
+                    Err(_) => return Err(FileManLog::FailedToReadFile { path: self.0.clone() }),
+                }
+            },
+        };
+
+        let lines: Vec<String> = text.lines().map(|each_line| each_line.to_string()).collect();
+        let skipped_len = lines.len().saturating_sub(n);
+        return Ok(lines.into_iter().skip(skipped_len).collect());
+    }
+
+    // note: 空のファイルの場合は None を返す
+    pub fn read_first_line(&self) -> FileManResult<Option<String>> {
+        self.ensure_exists()?;
+        self.ensure_be_file()?;
+
+        let mut reader = BufReader::new(self.open()?);
+        let mut line = String::new();
+
+        return match reader.read_line(&mut line) {
+            Ok(0) => Ok(None),
+            Ok(_) => Ok(Some(line.trim_end_matches(['\n', '\r']).to_string())),
+            Err(_) => Err(FileManLog::FailedToReadFile { path: self.0.clone() }),
+        };
+    }
+
+    pub fn read_numbered_lines(&self) -> FileManResult<Vec<(usize, String)>> {
+        let lines = self.read_lines()?;
+        return Ok(lines.into_iter().enumerate().map(|(i, each_line)| (i + 1, each_line)).collect());
+    }
+
+    // note: 区切り文字をプラットフォーム既定のものに揃えて表示する
+    #[cfg(windows)]
+    pub fn display_native(&self) -> String {
+        return self.0.replace('/', "\\");
+    }
+
+    #[cfg(not(windows))]
+    pub fn display_native(&self) -> String {
+        return self.0.clone();
+    }
+
+    // note: プラットフォームに依らず区切り文字を "/" に揃えて表示する
+    pub fn display_posix(&self) -> String {
+        return self.0.replace('\\', "/");
+    }
+
+    // note: 大文字と小文字を区別せずに比較し, 拡張子がない場合は "" と一致する
+    pub fn extension_matches(&self, exts: &[&str]) -> bool {
+        let ext = match self.1.extension() {
+            Some(v) => v.to_string_lossy().to_string(),
+            None => String::new(),
+        };
+
+        return exts.iter().any(|each_ext| each_ext.eq_ignore_ascii_case(&ext));
+    }
+
     pub fn change_extension(&self, new_ext: &str) -> String {
         let split_path: Vec<&str> = self.0.split(".").collect();
 
@@ -237,6 +1126,155 @@ impl FilePath {
         return self.0[0..self.0.len() - old_ext.len()].to_string() + new_ext;
     }
 
+    // note: ディレクトリは変えずにファイル名のみを変更する
+    pub fn rename_in_place(&self, new_name: &str) -> FileManResult<FilePath> {
+        self.ensure_exists()?;
+
+        let new_path = FilePath::from(self.1.with_file_name(new_name));
+
+        if new_path.exists() {
+            return Err(FileManLog::DestinationExists { path: new_path.0 });
+        }
+
+        return match rename(&self.1, &new_path.1) {
+            Ok(()) => Ok(new_path),
+            Err(_) => Err(FileManLog::FailedToRenameFile { path: self.0.clone() }),
+        };
+    }
+
+    pub fn open(&self) -> FileManResult<File> {
+        return match File::open(&self.0) {
+            Ok(v) => Ok(v),
+            Err(_) => Err(FileManLog::FailedToOpenFile { path: self.0.clone() }),
+        };
+    }
+
+    // note: ファイルが存在しない場合は新規作成する
+    pub fn open_append(&self) -> FileManResult<File> {
+        return match OpenOptions::new().append(true).create(true).open(&self.0) {
+            Ok(v) => Ok(v),
+            Err(_) => Err(FileManLog::FailedToOpenFile { path: self.0.clone() }),
+        };
+    }
+
+    // note: f の実行中はファイルに排他的なアドバイザリロックをかける
+    // ファイルが存在しない場合は作成する
+    #[cfg(feature = "locking")]
+    pub fn with_lock<R>(&self, f: impl FnOnce() -> R) -> FileManResult<R> {
+        use fs2::FileExt;
+
+        let file = self.open_append()?;
+
+        if file.lock_exclusive().is_err() {
+            return Err(FileManLog::FailedToLockFile { path: self.0.clone() });
+        }
+
+        let result = f();
+
+        return match file.unlock() {
+            Ok(()) => Ok(result),
+            Err(_) => Err(FileManLog::FailedToLockFile { path: self.0.clone() }),
+        };
+    }
+
+    // note: ごみ箱を利用できない場合もファイルを完全には削除せずエラーを返す
+    #[cfg(feature = "trash")]
+    pub fn move_to_trash(&self) -> FileManResult<()> {
+        self.ensure_exists()?;
+
+        return match trash::delete(&self.1) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(FileManLog::FailedToMoveToTrash { path: self.0.clone() }),
+        };
+    }
+
+    // note: ファイルが存在しない場合は作成し, 末尾に line と改行を追記する
+    pub fn append_line(&self, line: &str) -> FileManResult<()> {
+        let mut file = self.open_append()?;
+
+        return match writeln!(file, "{}", line) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(FileManLog::FailedToWriteFile { path: self.0.clone() }),
+        };
+    }
+
+    // note: 既に .bak が存在する場合は .bak.1, .bak.2, ... とする
+    pub fn backup(&self) -> FileManResult<FilePath> {
+        self.ensure_exists()?;
+        self.ensure_be_file()?;
+
+        let mut backup_path = FilePath::new(format!("{}.bak", self.0));
+        let mut backup_num = 1;
+
+        while backup_path.exists() {
+            backup_path = FilePath::new(format!("{}.bak.{}", self.0, backup_num));
+            backup_num += 1;
+        }
+
+        return match std::fs::copy(&self.1, &backup_path.1) {
+            Ok(_) => Ok(backup_path),
+            Err(_) => Err(FileManLog::FailedToCopyFile { path: self.0.clone() }),
+        };
+    }
+
+    pub fn file_name(&self) -> Option<String> {
+        return self.1.file_name().map(|v| v.to_string_lossy().to_string());
+    }
+
+    // note: ファイル名を保ったまま dir の直下にコピーし, コピー先のパスを返す
+    pub fn copy_into_dir(&self, dir: &FilePath) -> FileManResult<FilePath> {
+        self.ensure_exists()?;
+        self.ensure_be_file()?;
+        dir.ensure_exists()?;
+        dir.ensure_be_dir()?;
+
+        let file_name = match self.file_name() {
+            Some(v) => v,
+            None => return Err(FileManLog::ExpectedFilePathNotDirectoryPath),
+        };
+
+        let dest_path = FilePath::from(dir.1.join(file_name));
+
+        return match std::fs::copy(&self.1, &dest_path.1) {
+            Ok(_) => Ok(dest_path),
+            Err(_) => Err(FileManLog::FailedToCopyFile { path: self.0.clone() }),
+        };
+    }
+
+    // note: ディレクトリ構造を保ったまま dest_dir 下にコピーする
+    pub fn copy_dir(&self, dest_dir: &FilePath) -> FileManResult<()> {
+        return self.copy_dir_with_progress(dest_dir, |_, _, _| ());
+    }
+
+    // note: ファイルをコピーするごとに on_each(処理済み数, 総数, コピー元のパス) を呼ぶ
+    pub fn copy_dir_with_progress(&self, dest_dir: &FilePath, mut on_each: impl FnMut(usize, usize, &FilePath)) -> FileManResult<()> {
+        let paths = self.walk()?;
+        let total = paths.len();
+
+        for (i, each_path) in paths.iter().enumerate() {
+            let rel_path_obj = match each_path.1.strip_prefix(&self.1) {
+                Ok(v) => v,
+                Err(_) => return Err(FileManLog::FailedToCopyFile { path: each_path.0.clone() }),
+            };
+
+            let dest_path = FilePath::from(dest_dir.1.join(rel_path_obj));
+
+            if let Some(v) = dest_path.1.parent() {
+                if create_dir_all(v).is_err() {
+                    return Err(FileManLog::FailedToWriteFile { path: dest_path.0.clone() });
+                }
+            }
+
+            if std::fs::copy(&each_path.1, &dest_path.1).is_err() {
+                return Err(FileManLog::FailedToCopyFile { path: each_path.0.clone() });
+            }
+
+            on_each(i + 1, total, each_path);
+        }
+
+        return Ok(());
+    }
+
     pub fn create_file(&self) -> FileManResult<File> {
         return match File::create(&self.0) {
             Ok(v) => Ok(v),
@@ -244,10 +1282,38 @@ impl FilePath {
         };
     }
 
+    // note: 内容をそのまま書き込み, 末尾の改行は調整しない
     pub fn write(&self, content: &String) -> FileManResult<()> {
         return self.write_bytes(content.as_bytes());
     }
 
+    // note: 末尾の改行がちょうど 1 つになるように書き込む
+    pub fn write_line(&self, content: &str) -> FileManResult<()> {
+        return self.write(&format!("{}\n", content.trim_end_matches('\n')));
+    }
+
+    // note: 混在した改行コードを ending に統一して書き込む
+    pub fn write_with_line_endings(&self, content: &str, ending: LineEnding) -> FileManResult<()> {
+        let normalized_content = content.replace("\r\n", "\n").replace('\n', ending.as_str());
+        return self.write(&normalized_content);
+    }
+
+    // note: 各行の末尾に改行を付けて書き込む (POSIX に合わせて最終行の後にも改行を出力する)
+    pub fn write_lines(&self, lines: &[String]) -> FileManResult<()> {
+        let mut writer = BufWriter::new(self.create_file()?);
+
+        for each_line in lines {
+            if writeln!(writer, "{}", each_line).is_err() {
+                return Err(FileManLog::FailedToWriteFile { path: self.0.clone() });
+            }
+        }
+
+        return match writer.flush() {
+            Ok(()) => Ok(()),
+            Err(_) => Err(FileManLog::FailedToWriteFile { path: self.0.clone() }),
+        };
+    }
+
     pub fn write_bytes(&self, bytes: &[u8]) -> FileManResult<()> {
         let mut file = self.create_file()?;
 
@@ -259,6 +1325,103 @@ impl FilePath {
         return Ok(());
     }
 
+    #[cfg(feature = "serde")]
+    pub fn read_json<T: serde::de::DeserializeOwned>(&self) -> FileManResult<T> {
+        let content = self.read()?;
+
+        return match serde_json::from_str(&content) {
+            Ok(v) => Ok(v),
+            Err(_) => Err(FileManLog::FailedToParseFile { path: self.0.clone() }),
+        };
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn write_json<T: serde::Serialize>(&self, value: &T) -> FileManResult<()> {
+        let content = match serde_json::to_string(value) {
+            Ok(v) => v,
+            Err(_) => return Err(FileManLog::FailedToSerialize { path: self.0.clone() }),
+        };
+
+        return self.write(&content);
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn write_json_pretty<T: serde::Serialize>(&self, value: &T) -> FileManResult<()> {
+        let content = match serde_json::to_string_pretty(value) {
+            Ok(v) => v,
+            Err(_) => return Err(FileManLog::FailedToSerialize { path: self.0.clone() }),
+        };
+
+        return self.write(&content);
+    }
+
+    #[cfg(feature = "toml")]
+    pub fn read_toml<T: serde::de::DeserializeOwned>(&self) -> FileManResult<T> {
+        let content = self.read()?;
+
+        return match toml::from_str(&content) {
+            Ok(v) => Ok(v),
+            Err(e) => match e.span() {
+                Some(span) => {
+                    let (line, column) = FilePath::get_line_column_at(&content, span.start);
+                    Err(FileManLog::FailedToParseFileAt { path: self.0.clone(), line: line, column: column })
+                },
+                None => Err(FileManLog::FailedToParseFile { path: self.0.clone() }),
+            },
+        };
+    }
+
+    #[cfg(feature = "yaml")]
+    pub fn read_yaml<T: serde::de::DeserializeOwned>(&self) -> FileManResult<T> {
+        let content = self.read()?;
+
+        return match serde_yaml::from_str(&content) {
+            Ok(v) => Ok(v),
+            Err(e) => match e.location() {
+                Some(location) => Err(FileManLog::FailedToParseFileAt { path: self.0.clone(), line: location.line(), column: location.column() }),
+                None => Err(FileManLog::FailedToParseFile { path: self.0.clone() }),
+            },
+        };
+    }
+
+    // note: バイト位置を 1 始まりの行番号と列番号に変換する
+    #[cfg(feature = "toml")]
+    fn get_line_column_at(content: &str, offset: usize) -> (usize, usize) {
+        let preceding = &content[..offset.min(content.len())];
+        let line = preceding.matches('\n').count() + 1;
+
+        let column = match preceding.rfind('\n') {
+            Some(i) => preceding[i + 1..].chars().count() + 1,
+            None => preceding.chars().count() + 1,
+        };
+
+        return (line, column);
+    }
+
+    // note: 一時的なエラーの場合のみ delay を空けて再試行する
+    pub fn write_with_retry(&self, bytes: &[u8], attempts: usize, delay: Duration) -> FileManResult<()> {
+        let mut remaining_attempts = attempts.max(1);
+
+        loop {
+            let result = File::create(&self.1).and_then(|mut file| file.write_all(bytes));
+
+            let err = match result {
+                Ok(()) => return Ok(()),
+                Err(e) => e,
+            };
+
+            let is_transient = matches!(err.kind(), ErrorKind::Interrupted | ErrorKind::WouldBlock | ErrorKind::TimedOut);
+
+            remaining_attempts -= 1;
+
+            if !is_transient || remaining_attempts == 0 {
+                return Err(FileManLog::FailedToWriteFile { path: self.0.clone() });
+            }
+
+            thread::sleep(delay);
+        }
+    }
+
     pub fn ensure_exists(&self) -> FileManResult<()> {
         return if self.exists() {
             Ok(())
@@ -274,6 +1437,14 @@ impl FilePath {
             Err(FileManLog::ExpectedFilePathNotDirectoryPath)
         };
     }
+
+    pub fn ensure_be_dir(&self) -> FileManResult<()> {
+        return if self.is_dir() {
+            Ok(())
+        } else {
+            Err(FileManLog::ExpectedDirectoryPathNotFilePath { path: self.0.clone() })
+        };
+    }
 }
 
 impl Display for FilePath {
@@ -281,3 +1452,146 @@ impl Display for FilePath {
         return write!(f, "{}", self.0);
     }
 }
+
+// note: FilePath を生成せずに &str のパスでファイルを扱うための簡易 API
+// 各メソッドは FilePath の処理に委譲するため, エラーも FilePath と同じものを返す
+pub struct FileMan;
+
+impl FileMan {
+    pub fn read_all(path: &str) -> FileManResult<String> {
+        return FilePath::new(path.to_string()).read();
+    }
+
+    pub fn write_all(path: &str, content: &str) -> FileManResult<()> {
+        return FilePath::new(path.to_string()).write(&content.to_string());
+    }
+
+    // note: 各行の末尾に改行を付けて書き込む
+    pub fn write_lines(path: &str, lines: &[String]) -> FileManResult<()> {
+        return FilePath::new(path.to_string()).write_lines(lines);
+    }
+}
+
+// note: drop 時にファイルを削除する一時ファイルパス
+pub struct TempFilePath(FilePath);
+
+impl TempFilePath {
+    pub fn new(prefix: &str) -> FileManResult<TempFilePath> {
+        return Ok(TempFilePath(FilePath::temp_file(prefix)?));
+    }
+}
+
+impl Deref for TempFilePath {
+    type Target = FilePath;
+
+    fn deref(&self) -> &FilePath {
+        return &self.0;
+    }
+}
+
+impl Drop for TempFilePath {
+    fn drop(&mut self) {
+        let _ = remove_file(&self.0.1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_same_as_matches_relative_and_absolute_paths() {
+        let rel_path = FilePath::new("Cargo.toml".to_string());
+        let abs_path = rel_path.to_absolute().unwrap();
+
+        assert!(rel_path.is_same_as(&abs_path).unwrap());
+        assert!(!rel_path.is_same_as(&FilePath::new("src".to_string())).unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn is_same_as_matches_symlinks_to_same_file() {
+        let target_path = TempFilePath::new("cons-util-test").unwrap();
+        let link_path_a = TempFilePath::new("cons-util-test").unwrap();
+        let link_path_b = TempFilePath::new("cons-util-test").unwrap();
+
+        for each_link in [&link_path_a, &link_path_b] {
+            remove_file(&each_link.1).unwrap();
+            std::os::unix::fs::symlink(&target_path.1, &each_link.1).unwrap();
+        }
+
+        assert!(link_path_a.is_same_as(&link_path_b).unwrap());
+        assert!(link_path_a.is_same_as(&target_path).unwrap());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn canonicalize_strips_verbatim_prefix() {
+        let path = TempFilePath::new("cons-util-test").unwrap();
+        assert!(!path.canonicalize().unwrap().to_string().starts_with(r"\\?\"));
+
+        assert_eq!(FilePath::strip_verbatim_prefix(PathBuf::from(r"\\?\C:\dir\file")), PathBuf::from(r"C:\dir\file"));
+        assert_eq!(FilePath::strip_verbatim_prefix(PathBuf::from(r"\\?\UNC\server\share")), PathBuf::from(r"\\server\share"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn is_hidden_detects_dotfiles() {
+        let visible_path = TempFilePath::new("cons-util-test").unwrap();
+        let hidden_path = TempFilePath::new(".cons-util-test").unwrap();
+
+        assert!(!visible_path.is_hidden().unwrap());
+        assert!(hidden_path.is_hidden().unwrap());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn is_hidden_detects_hidden_attribute() {
+        let path = TempFilePath::new("cons-util-test").unwrap();
+        assert!(!path.is_hidden().unwrap());
+
+        let status = process::Command::new("attrib").arg("+h").arg(&path.1).status().unwrap();
+        assert!(status.success());
+        assert!(path.is_hidden().unwrap());
+    }
+
+    #[test]
+    fn write_with_line_endings_normalizes_mixed_endings() {
+        let path = TempFilePath::new("cons-util-test").unwrap();
+        let content = "a\r\nb\nc\r\n";
+
+        path.write_with_line_endings(content, LineEnding::CrLf).unwrap();
+        assert_eq!(path.read().unwrap(), "a\r\nb\r\nc\r\n");
+        assert_eq!(path.read_normalized().unwrap(), "a\nb\nc\n");
+
+        path.write_with_line_endings(content, LineEnding::Lf).unwrap();
+        assert_eq!(path.read().unwrap(), "a\nb\nc\n");
+    }
+
+    #[test]
+    fn walk_with_progress_reports_each_file_with_total() {
+        let mut progress = Vec::<(usize, usize)>::new();
+        let paths = FilePath::new("src".to_string()).walk_with_progress(|done, total, _| progress.push((done, total))).unwrap();
+
+        let total = paths.len();
+        assert!(total > 0);
+        assert_eq!(progress, (1..=total).map(|each_i| (each_i, total)).collect::<Vec<(usize, usize)>>());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn walk_skips_dangling_symlinks() {
+        let dir_path = FilePath::from(temp_dir().join(format!("cons-util-test-walk-{}", process::id())));
+        let src_dir_path = FilePath::from(dir_path.1.join("src"));
+        create_dir_all(&src_dir_path.1).unwrap();
+
+        FilePath::from(src_dir_path.1.join("file.txt")).write(&"abc".to_string()).unwrap();
+        std::os::unix::fs::symlink(src_dir_path.1.join("missing.txt"), src_dir_path.1.join("dangling.txt")).unwrap();
+
+        assert_eq!(src_dir_path.walk().unwrap().len(), 1);
+        assert_eq!(src_dir_path.dir_size().unwrap(), 3);
+        src_dir_path.copy_dir(&FilePath::from(dir_path.1.join("dest"))).unwrap();
+
+        remove_dir_all(&dir_path.1).unwrap();
+    }
+}
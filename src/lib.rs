@@ -25,10 +25,87 @@ impl<T, E: ConsoleLogTranslator> ConsoleResultConsumption<T> for Result<T, E> {
 
 #[derive(Clone, cons_util_derive::ConsoleLogTranslator, Debug, PartialEq)]
 pub enum InternalLog {
+    #[translate(
+        kind = "N",
+        en = "+{count} more",
+        ja = "他 {count} 件",
+    )]
+    DescriptionsOmitted { count: usize },
+
+    #[translate(
+        kind = "E",
+        en = "failed to connect to syslog",
+        ja = "syslog への接続に失敗しました",
+    )]
+    FailedToConnectToSyslog,
+
+    #[translate(
+        kind = "E",
+        en = "failed to write to syslog",
+        ja = "syslog への書き込みに失敗しました",
+    )]
+    FailedToWriteToSyslog,
+
+    #[translate(
+        kind = "E",
+        en = "invalid timestamp format `{format}`",
+        ja = "不正なタイムスタンプの書式 `{format}`",
+    )]
+    InvalidTimestampFormat { format: String },
+
+    #[translate(
+        kind = "N",
+        en = " * created at {time}",
+        ja = " * 作成日時: {time}",
+    )]
+    LogFileCreatedAt { time: String },
+
+    #[translate(
+        kind = "N",
+        en = " * generated by cons-util",
+        ja = " * cons-util により生成",
+    )]
+    LogFileGeneratedBy,
+
+    #[translate(
+        kind = "N",
+        en = "--- Log File ---",
+        ja = "--- ログファイル ---",
+    )]
+    LogFileHeader,
+
     #[translate(
         kind = "E",
         en = "log limit {log_limit} exceeded",
         ja = "ログ制限 {log_limit} を超過しました",
     )]
     LogLimitExceeded { log_limit: ConsoleLogLimit },
+
+    #[translate(
+        kind = "N",
+        en = "output truncated due to output size limit",
+        ja = "出力サイズの上限により出力を打ち切りました",
+    )]
+    OutputTruncated,
+
+    #[translate(
+        kind = "W",
+        en = "translation key `{key}` is registered with different messages\n\tlanguage: {language}",
+        ja = "翻訳キー `{key}` が異なるメッセージで登録されています\n\t言語: {language}",
+    )]
+    TranslationKeyCollision { key: String, language: String },
+
+    #[translate(
+        kind = "N",
+        en = "ungrouped",
+        ja = "グループなし",
+    )]
+    UngroupedLogs,
+
+    #[translate(
+        kind = "E",
+        en = "unknown log kind `{value}`\n\texpected: error, warning, note, name:color:severity",
+        ja = "不明なログの種類 `{value}`\n\t有効な値: error, warning, note, 名前:色:深刻度",
+    )]
+    UnknownLogKind { value: String },
 }